@@ -1,4 +1,5 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::io::IsTerminal;
 
 #[derive(Parser)]
 #[command(name = "snail", version)]
@@ -30,6 +31,35 @@ pub enum Commands {
         #[command(subcommand)]
         action: GtdAction,
     },
+    /// Next actions grouped by context
+    Next {
+        /// Contexts to show (e.g. @phone @office); all when omitted
+        contexts: Vec<String>,
+        /// Only tasks whose estimate fits in this many minutes
+        #[arg(short, long)]
+        time: Option<u32>,
+        /// Only tasks that need at most this much energy
+        #[arg(short, long, value_enum)]
+        energy: Option<Energy>,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Append a timestamped entry to today's daily report log
+    Log {
+        /// Message (read from stdin when omitted)
+        message: Option<String>,
+        /// Also log the entry in this todo's Log section
+        #[arg(short, long)]
+        todo: Option<String>,
+    },
+    /// Day-by-day timeline of due and review dates
+    Agenda {
+        /// Number of days to show after today
+        #[arg(short, long, default_value_t = 7)]
+        days: u32,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
 }
 
 #[derive(Subcommand)]
@@ -43,11 +73,22 @@ pub enum MemoAction {
         no_edit: bool,
     },
     /// List all memos
-    List,
+    List {
+        /// Filter expression (e.g., "tag:idea", "created>=-7d", "meeting OR 会議")
+        #[arg(short, long)]
+        filter: Vec<String>,
+        /// Include archived memos and reference material
+        #[arg(short, long)]
+        archive: bool,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
     /// Search memos
     Search {
         /// Search keyword
         keyword: String,
+        #[command(flatten)]
+        output: OutputArgs,
     },
 }
 
@@ -60,21 +101,88 @@ pub enum TodoAction {
         /// Project name
         #[arg(short, long)]
         project: Option<String>,
+        /// Due date (e.g., "tomorrow", "fri", "+3d", "next month", "2026-11-01")
+        #[arg(short, long, allow_hyphen_values = true)]
+        due: Option<String>,
         /// Do not open editor after creating
         #[arg(short = 'n', long)]
         no_edit: bool,
     },
     /// List all todo tasks
     List {
-        /// Filter option (e.g., "due:today", "status:next", "project:hoge")
+        /// Filter expression (e.g., "due:today", "status:next", "project:hoge",
+        /// "due<=+3d AND (tag:urgent OR context:@office)", "NOT estimate>30m")
         #[arg(short, long)]
         filter: Vec<String>,
+        /// Include archived (done / canceled) todos
+        #[arg(short, long)]
+        archive: bool,
+        /// Hide todos whose `depends_on:` tasks are still open
+        #[arg(long)]
+        hide_blocked: bool,
+        /// Include todos deferred with a future `review_date:`
+        #[arg(long)]
+        deferred: bool,
+        #[command(flatten)]
+        output: OutputArgs,
     },
     /// Mark a todo as done
     Done {
         /// Path to the todo file
         file: String,
     },
+    /// Move a todo to another GTD list and update its status
+    Move {
+        /// Path, filename or date (YYYY-MM-DD) of the todo
+        file: String,
+        /// Destination: inbox, next, waiting, someday or project:<name>
+        #[arg(short, long)]
+        to: String,
+    },
+    /// Delegate a todo and move it to the waiting-for list
+    Delegate {
+        /// Path, filename or date (YYYY-MM-DD) of the todo
+        file: String,
+        /// Person the task is waiting on
+        #[arg(short, long)]
+        to: String,
+        /// Follow-up date expression (e.g. "fri", "+1w", "2025-12-01")
+        #[arg(short, long, allow_hyphen_values = true)]
+        follow_up: Option<String>,
+    },
+    /// Cancel a todo and move it to the archive
+    Cancel {
+        /// Path, filename or date (YYYY-MM-DD) of the todo
+        file: String,
+    },
+    /// Turn a todo into a project; unchecked Project section items become todos
+    Promote {
+        /// Path, filename or date (YYYY-MM-DD) of the todo
+        file: String,
+        /// Project name (defaults to the todo title)
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Set a todo's due date
+    Due {
+        /// Path, filename or date (YYYY-MM-DD) of the todo
+        file: String,
+        /// Date expression (e.g., "tomorrow", "fri", "+3d", "next month", "2026-11-01", "none")
+        #[arg(allow_hyphen_values = true)]
+        date: String,
+    },
+    /// Toggle the nth checkbox (`- [ ]`) in a todo
+    Check {
+        /// Path, filename or date (YYYY-MM-DD) of the todo
+        file: String,
+        /// Checkbox number, counting from 1 in file order
+        n: usize,
+    },
+    /// Reopen an archived todo and move it back where it came from
+    Reopen {
+        /// Path, filename or date (YYYY-MM-DD) of the archived todo
+        file: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -88,7 +196,10 @@ pub enum ProjectAction {
         no_edit: bool,
     },
     /// List all projects
-    List,
+    List {
+        #[command(flatten)]
+        output: OutputArgs,
+    },
     /// Show project details
     Show {
         /// Project name
@@ -107,15 +218,89 @@ pub enum GtdAction {
     Weekly,
     /// Monthly review
     Monthly,
+    /// Process inbox items one by one (clarify)
+    Process,
+    /// List deferred items whose review_date has arrived
+    Tickler {
+        /// Move them back to the inbox and clear review_date
+        #[arg(short, long = "move")]
+        move_to_inbox: bool,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum TodayAction {
     /// List today's tasks
-    List,
+    List {
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Create today's report, carrying over unfinished tasks
+    Start,
     /// Add a task to today's daily report
     Add {
         /// Task description
         task: String,
     },
+    /// Check off a task and move it to the Done section
+    Done {
+        /// Task number (as shown by `today list`) or text to match
+        item: String,
+    },
+    /// Remove a task from the TODO section
+    Remove {
+        /// Task number (as shown by `today list`) or text to match
+        item: String,
+    },
+    /// Change a task's text, or open the editor at its line
+    Edit {
+        /// Task number (as shown by `today list`) or text to match
+        item: String,
+        /// New task text (opens the editor when omitted)
+        text: Option<String>,
+    },
+}
+
+/// Energy a task needs: `energy:` in the frontmatter, or judged from `estimate:`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Energy {
+    /// Quick tasks (estimate up to 15 minutes)
+    Low,
+    /// Up to an hour
+    Medium,
+    /// Anything longer
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable list
+    Text,
+    /// JSON array including parsed frontmatter
+    Json,
+    /// Comma separated values with a header row
+    Csv,
+    /// Tab separated values with a header row
+    Tsv,
+    /// One absolute path per line
+    Paths,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct OutputArgs {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+    /// Do not prompt to open a file (implied when stdout is not a terminal)
+    #[arg(long)]
+    pub no_interactive: bool,
+}
+
+impl OutputArgs {
+    /// Whether to show the "Open file" prompt after a text listing
+    pub fn interactive(&self) -> bool {
+        self.format == OutputFormat::Text
+            && !self.no_interactive
+            && std::io::stdout().is_terminal()
+    }
 }
@@ -0,0 +1,168 @@
+use anyhow::{Context, Result};
+use chrono::{Days, Local, NaiveDate};
+use std::fs;
+use std::path::PathBuf;
+use crate::cli::{OutputArgs, OutputFormat};
+use crate::commands::gtd::parse_todo_item;
+use crate::commands::todo::{collect_active_todos, collect_todos, TodoItem};
+use crate::config::Config;
+use crate::markdown::Document;
+use crate::output::{print_records, Record};
+use crate::utils::{open_editor, prompt_selection};
+
+/// One line of the agenda
+struct AgendaEntry<'a> {
+    date: NaiveDate,
+    /// "due", "review" or "daily"
+    kind: &'static str,
+    title: String,
+    /// Checked state of a daily report item
+    done: bool,
+    path: PathBuf,
+    todo: Option<&'a TodoItem>,
+}
+
+pub fn agenda(days: u32, output: &OutputArgs, config: &Config) -> Result<()> {
+    let date_format = &config.general.date_format;
+    let today = Local::now().date_naive();
+    let end = today
+        .checked_add_days(Days::new(days as u64))
+        .ok_or_else(|| anyhow::anyhow!("--days {} reaches past the last supported date", days))?;
+    let root_dir = config.root_dir()?;
+
+    // Someday items are included so their review dates show up
+    let mut todos = collect_active_todos(config)?;
+    let someday_dir = config.someday_dir()?;
+    if someday_dir.exists() {
+        collect_todos(&someday_dir, &mut todos)?;
+    }
+
+    let mut entries: Vec<AgendaEntry> = Vec::new();
+    for todo in &todos {
+        let fm = &todo.note.frontmatter;
+        if let Some(due) = fm.due_date(date_format).filter(|d| *d <= end) {
+            entries.push(AgendaEntry {
+                date: due,
+                kind: "due",
+                title: todo.title.clone(),
+                done: false,
+                path: todo.path.clone(),
+                todo: Some(todo),
+            });
+        }
+        // A past review date is not overdue: the todo just shows up again
+        // (see `is_deferred`), so only upcoming reviews are listed
+        if let Some(review) = fm.review_date(date_format).filter(|d| (today..=end).contains(d)) {
+            entries.push(AgendaEntry {
+                date: review,
+                kind: "review",
+                title: todo.title.clone(),
+                done: false,
+                path: todo.path.clone(),
+                todo: Some(todo),
+            });
+        }
+    }
+
+    // Items of the daily report's TODO section (`daily_report.todo`)
+    let date = today.format(date_format).to_string();
+    let report_path = config.inbox_dir()?.join(format!("{}-daily_report.md", date));
+    if report_path.exists() {
+        let content = fs::read_to_string(&report_path)
+            .with_context(|| format!("Failed to read daily report: {:?}", report_path))?;
+        for item in Document::parse(&content).section_items(&config.daily_report.todo) {
+            let (done, text) = parse_todo_item(&item.text);
+            entries.push(AgendaEntry {
+                date: today,
+                kind: "daily",
+                title: text.to_string(),
+                done,
+                path: report_path.clone(),
+                todo: None,
+            });
+        }
+    }
+
+    entries.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.kind.cmp(b.kind)));
+
+    let section = |entry: &AgendaEntry| {
+        if entry.date < today {
+            "overdue"
+        } else if entry.date == today {
+            "today"
+        } else {
+            "upcoming"
+        }
+    };
+
+    if output.format != OutputFormat::Text {
+        let records: Vec<Record> = entries
+            .iter()
+            .map(|e| {
+                let status = e.todo.map(|t| t.status.as_str()).unwrap_or("");
+                let record = Record::new()
+                    .field("date", e.date.format(date_format).to_string())
+                    .field("section", section(e))
+                    .field("kind", e.kind)
+                    .field("title", e.title.as_str())
+                    .field("status", status)
+                    .field("done", e.done)
+                    .paths(&e.path, &root_dir);
+                match e.todo {
+                    Some(todo) => record.frontmatter(&todo.note.frontmatter),
+                    None => record,
+                }
+            })
+            .collect();
+        return print_records(output.format, &records);
+    }
+
+    // Numbered entries that can be opened from the prompt
+    let mut openable: Vec<PathBuf> = Vec::new();
+    let mut print_entry = |entry: &AgendaEntry, show_date: bool| {
+        openable.push(entry.path.clone());
+        let label = match entry.kind {
+            "daily" if entry.done => "[x]",
+            "daily" => "[ ]",
+            kind => kind,
+        };
+        let date_str = if show_date {
+            format!("{} ", entry.date.format(date_format))
+        } else {
+            String::new()
+        };
+        println!("  {:>2}. {}{:<6} {}", openable.len(), date_str, label, entry.title);
+    };
+
+    let overdue: Vec<&AgendaEntry> = entries.iter().filter(|e| e.date < today).collect();
+    if !overdue.is_empty() {
+        println!("Overdue");
+        for entry in overdue {
+            print_entry(entry, true);
+        }
+        println!();
+    }
+
+    for offset in 0..=days as u64 {
+        let day = today + Days::new(offset);
+        let heading = if offset == 0 { "Today " } else { "" };
+        println!("{}{} ({})", heading, day.format(date_format), day.format("%a"));
+
+        let day_entries: Vec<&AgendaEntry> = entries.iter().filter(|e| e.date == day).collect();
+        if day_entries.is_empty() {
+            println!("   -");
+        }
+        for entry in day_entries {
+            print_entry(entry, false);
+        }
+    }
+
+    if output.interactive() && !openable.is_empty() {
+        println!();
+        if let Some(selection) = prompt_selection("Open file", openable.len())? {
+            open_editor(&openable[selection], &config.general.editor)?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,10 +1,19 @@
 use anyhow::{Context, Result};
-use chrono::{Datelike, Local};
+use chrono::{Datelike, Local, Months, NaiveDate, Weekday};
 use std::fs::{self, OpenOptions};
 use std::io::{self, Write as IoWrite};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
+use crate::commands::todo::{archive_done, archive_note, collect_active_todos, collect_all_todos_recursive, collect_todos, complete, is_deferred, move_note, promote_note, TodoItem};
+use crate::cli::{OutputArgs, OutputFormat};
 use crate::config::Config;
-use crate::utils::{get_current_date, open_editor};
+use crate::markdown::{self, Document};
+use crate::note::{collect_notes, Note};
+use crate::output::{print_records, Record};
+use crate::utils::{apply_replacements, create_file_from_template, get_current_date, open_editor, open_editor_at_line, sanitize_filename};
+
+/// Built-in weekly report used when the configured template file is missing
+const DEFAULT_WEEKLY_REPORT_TEMPLATE: &str = include_str!("../../templates/weekly_report.md");
 
 use crossterm::{
     event::{self, Event, KeyCode, KeyEventKind},
@@ -34,7 +43,7 @@ struct ReviewPrompt {
     responses: Vec<String>,
 }
 
-pub fn today_list(config: &Config) -> Result<()> {
+pub fn today_list(output: &OutputArgs, config: &Config) -> Result<()> {
     let date = get_current_date(&config.general.date_format);
     let filename = format!("{}-daily_report.md", date);
 
@@ -42,6 +51,9 @@ pub fn today_list(config: &Config) -> Result<()> {
     let file_path = inbox_dir.join(&filename);
 
     if !file_path.exists() {
+        if output.format != OutputFormat::Text {
+            return print_records(output.format, &[]);
+        }
         println!("No daily report found for today ({}).", date);
         return Ok(());
     }
@@ -49,46 +61,54 @@ pub fn today_list(config: &Config) -> Result<()> {
     let content = fs::read_to_string(&file_path)
         .with_context(|| format!("Failed to read daily report: {:?}", file_path))?;
 
+    let todos = Document::parse(&content).section_items(&config.daily_report.todo);
+
+    if output.format != OutputFormat::Text {
+        let note = Note::parse(file_path.clone(), &content);
+        let root_dir = config.root_dir()?;
+        let records: Vec<Record> = todos
+            .iter()
+            .enumerate()
+            .map(|(i, task)| {
+                let (done, text) = parse_todo_item(&task.text);
+                Record::new()
+                    .field("index", i + 1)
+                    .field("done", done)
+                    .field("text", text)
+                    .field("date", date.as_str())
+                    .paths(&file_path, &root_dir)
+                    .frontmatter(&note.frontmatter)
+            })
+            .collect();
+        return print_records(output.format, &records);
+    }
+
     println!("{} Daily Report:\n", date);
 
-    let todos = extract_todo_section(&content);
     if todos.is_empty() {
-        println!("No tasks in TODO section.");
+        println!("No tasks in {} section.", config.daily_report.todo);
     } else {
-        for task in &todos {
-            println!("{}", task);
+        for (i, task) in todos.iter().enumerate() {
+            println!("{:>2}. {}", i + 1, task.text);
         }
     }
 
     Ok(())
 }
 
-fn extract_todo_section(content: &str) -> Vec<String> {
-    let lines: Vec<&str> = content.lines().collect();
-    let mut todos = Vec::new();
-    let mut in_todo_section = false;
-
-    for line in lines {
-        // Match various TODO section headers
-        let line_lower = line.to_lowercase();
-        if line.starts_with("## ") && (line_lower.contains("todo") || line.contains("ToDo")) {
-            in_todo_section = true;
-            continue;
-        }
-
-        if in_todo_section {
-            if line.starts_with("## ") {
-                break;
-            }
-            if line.starts_with("- ") {
-                todos.push(line.to_string());
-            }
-        }
+/// Split a `- [ ] task` line into (checked, text)
+pub fn parse_todo_item(line: &str) -> (bool, &str) {
+    let item = line.trim_start_matches("- ");
+    if let Some(text) = item.strip_prefix("[ ] ") {
+        (false, text)
+    } else if let Some(text) = item.strip_prefix("[x] ").or_else(|| item.strip_prefix("[X] ")) {
+        (true, text)
+    } else {
+        (false, item)
     }
-
-    todos
 }
 
+
 pub fn today_add(task: &str, config: &Config) -> Result<()> {
     let date = get_current_date(&config.general.date_format);
     let filename = format!("{}-daily_report.md", date);
@@ -97,35 +117,18 @@ pub fn today_add(task: &str, config: &Config) -> Result<()> {
     let file_path = inbox_dir.join(&filename);
 
     if !file_path.exists() {
-        // Create new daily report from template
-        let template_path = config.get_template_path("daily_report")?;
-
-        let content = if template_path.exists() {
-            fs::read_to_string(&template_path)
-                .with_context(|| format!("Failed to read template: {:?}", template_path))?
-                .replace("{{date}}", &date)
-        } else {
-            // Default template
-            format!(
-                "---\ndate: {}\n---\n\n# {} Daily Report\n\n## TODO\n\n## Done\n\n## Memo\n",
-                date, date
-            )
-        };
-
-        fs::write(&file_path, content)
-            .with_context(|| format!("Failed to create daily report: {:?}", file_path))?;
-
-        println!("Created daily report: {}", file_path.display());
+        let carry_over = create_daily_report(&file_path, config)?;
+        print_created_report(&file_path, carry_over);
     }
 
     // Add task to TODO section
     let content = fs::read_to_string(&file_path)
         .with_context(|| format!("Failed to read daily report: {:?}", file_path))?;
 
-    let new_task = format!("- [ ] {}", task);
-    let updated_content = add_to_todo_section(&content, &new_task);
+    let mut doc = Document::parse(&content);
+    doc.append(&config.daily_report.todo, &format!("- [ ] {}", task));
 
-    fs::write(&file_path, updated_content)
+    fs::write(&file_path, doc.render())
         .with_context(|| format!("Failed to update daily report: {:?}", file_path))?;
 
     println!("Added task to daily report: {}", task);
@@ -135,37 +138,290 @@ pub fn today_add(task: &str, config: &Config) -> Result<()> {
     Ok(())
 }
 
-fn add_to_todo_section(content: &str, task: &str) -> String {
-    let lines: Vec<&str> = content.lines().collect();
-    let mut result = Vec::new();
-    let mut in_todo_section = false;
-    let mut task_added = false;
+/// Create today's daily report (carrying over unfinished tasks) and open it
+pub fn today_start(config: &Config) -> Result<()> {
+    let date = get_current_date(&config.general.date_format);
+    let file_path = config.inbox_dir()?.join(format!("{}-daily_report.md", date));
 
-    for line in lines {
-        if line.trim() == "## TODO" {
-            in_todo_section = true;
-            result.push(line.to_string());
-            continue;
+    if file_path.exists() {
+        println!("Daily report already exists: {}", file_path.display());
+    } else {
+        let carry_over = create_daily_report(&file_path, config)?;
+        print_created_report(&file_path, carry_over);
+    }
+
+    open_editor(&file_path, &config.general.editor)?;
+
+    Ok(())
+}
+
+/// Create today's daily report from the template. Unchecked TODO items of the
+/// most recent earlier report are carried over, tagged with the number of days
+/// they have been rolling, and the report links back to it. Prints nothing,
+/// so it can run under the `gtd process` TUI; see `print_created_report`.
+pub fn create_daily_report(file_path: &Path, config: &Config) -> Result<Option<CarryOver>> {
+    let date_format = &config.general.date_format;
+    let today = Local::now().date_naive();
+    let date = today.format(date_format).to_string();
+
+    let template_path = config.get_template_path("daily_report")?;
+    let sections = &config.daily_report;
+
+    let content = if template_path.exists() {
+        fs::read_to_string(&template_path)
+            .with_context(|| format!("Failed to read template: {:?}", template_path))?
+            .replace("{{date}}", &date)
+    } else {
+        // Default template
+        format!(
+            "---\ndate: {}\n---\n\n# {} Daily Report\n\n## {}\n\n## {}\n\n## {}\n\n## {}\n",
+            date, date, sections.todo, sections.done, sections.memo, sections.log
+        )
+    };
+    let mut doc = Document::parse(&content);
+
+    let mut carried = 0;
+    let previous = find_previous_daily_report(config, today)?;
+    if let Some((ref previous_path, previous_date)) = previous {
+        let previous_content = fs::read_to_string(previous_path)
+            .with_context(|| format!("Failed to read daily report: {:?}", previous_path))?;
+        let previous_doc = Document::parse(&previous_content);
+        let gap = (today - previous_date).num_days();
+
+        // Nested lines (sub-tasks, notes) travel with their item
+        for item in previous_doc.section_items(&sections.todo) {
+            let (done, text) = parse_todo_item(&item.text);
+            if done {
+                continue;
+            }
+            let (text, rolled) = split_rolled(text);
+            let mut block = vec![format!("- [ ] {} (rolled {}d)", text, rolled + gap)];
+            block.extend(previous_doc.lines(item.line + 1, item.end).iter().cloned());
+            doc.append(&sections.todo, &block.join("\n"));
+            carried += 1;
         }
 
-        if in_todo_section && line.starts_with("##") {
-            // Reached next section, add task before it
-            if !task_added {
-                result.push(task.to_string());
-                task_added = true;
+        let stem = previous_path.file_stem().and_then(|n| n.to_str()).unwrap_or_default();
+        doc.insert_below_title(&format!("Previous: [[{}]]", stem));
+    }
+
+    fs::write(file_path, doc.render())
+        .with_context(|| format!("Failed to create daily report: {:?}", file_path))?;
+
+    Ok(previous.map(|(from, _)| CarryOver { from, tasks: carried }))
+}
+
+/// Unfinished tasks a new daily report took over from the previous one
+pub struct CarryOver {
+    pub from: PathBuf,
+    pub tasks: usize,
+}
+
+pub fn print_created_report(file_path: &Path, carry_over: Option<CarryOver>) {
+    println!("Created daily report: {}", file_path.display());
+    if let Some(carry_over) = carry_over {
+        println!("Carried over {} unfinished task(s) from {}", carry_over.tasks, carry_over.from.display());
+    }
+}
+
+/// Add `- [x] [[stem]] title` for a done or canceled todo to today's daily
+/// report (the `daily_report.done` or `.canceled` section), unless
+/// `gtd.record_completed` is off or the todo is already listed there.
+/// A missing report is created silently, as this also runs under `gtd process`.
+pub fn record_closed_todo(stem: &str, title: &str, status: &str, config: &Config) -> Result<()> {
+    if !config.gtd.record_completed {
+        return Ok(());
+    }
+
+    let date = get_current_date(&config.general.date_format);
+    let file_path = config.inbox_dir()?.join(format!("{}-daily_report.md", date));
+    if !file_path.exists() {
+        create_daily_report(&file_path, config)?;
+    }
+
+    let content = fs::read_to_string(&file_path)
+        .with_context(|| format!("Failed to read daily report: {:?}", file_path))?;
+
+    let heading = if status == "canceled" {
+        &config.daily_report.canceled
+    } else {
+        &config.daily_report.done
+    };
+    let mut doc = Document::parse(&content);
+
+    // `gtd today done` may have moved the linking item there already
+    let link = format!("[[{}]]", stem);
+    if doc.section_items(heading).iter().any(|item| item.text.contains(&link)) {
+        return Ok(());
+    }
+
+    doc.append(heading, &format!("- [x] {} {}", link, title));
+
+    fs::write(&file_path, doc.render())
+        .with_context(|| format!("Failed to update daily report: {:?}", file_path))?;
+
+    Ok(())
+}
+
+/// The latest daily report (inbox or archive) dated before `today`
+fn find_previous_daily_report(config: &Config, today: NaiveDate) -> Result<Option<(PathBuf, NaiveDate)>> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for dir in [config.inbox_dir()?, config.archive_dir()?] {
+        if dir.exists() {
+            collect_daily_reports(&dir, &mut files)?;
+        }
+    }
+
+    let previous = files
+        .into_iter()
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?;
+            let date = stem.strip_suffix("-daily_report")?;
+            let date = NaiveDate::parse_from_str(date, &config.general.date_format).ok()?;
+            Some((path, date))
+        })
+        .filter(|(_, date)| *date < today)
+        .max_by_key(|(_, date)| *date);
+
+    Ok(previous)
+}
+
+/// Split `task (rolled 3d)` into ("task", 3)
+fn split_rolled(text: &str) -> (&str, i64) {
+    text.strip_suffix("d)")
+        .and_then(|rest| rest.rsplit_once(" (rolled "))
+        .and_then(|(task, days)| Some((task, days.parse().ok()?)))
+        .unwrap_or((text, 0))
+}
+
+/// Check off a daily report task and move it to the Done section with the
+/// time. Todos it links to with `[[...]]` are marked done as well.
+pub fn today_done(item: &str, config: &Config) -> Result<()> {
+    let sections = &config.daily_report;
+    let (file_path, mut doc) = read_today_report(config)?;
+    let item = select_today_item(&doc, item, &sections.todo)?;
+    let text = parse_todo_item(&item.text).1.to_string();
+
+    // Nested lines move along with the item
+    let mut block = doc.remove(&item);
+    let time = Local::now().format("%H:%M");
+    block[0] = format!("- [x] {} {}", time, text);
+    doc.append(&sections.done, &block.join("\n"));
+
+    fs::write(&file_path, doc.render())
+        .with_context(|| format!("Failed to update daily report: {:?}", file_path))?;
+    println!("Done: {}", text);
+
+    // Archive linked todos through the usual `todo done` path
+    let links = wiki_links(&text);
+    if !links.is_empty() {
+        let todos = collect_active_todos(config)?;
+        for link in links {
+            match todos.iter().find(|t| t.note.stem() == link) {
+                Some(todo) => complete(&todo.path, config)?,
+                None => println!("No open todo found for [[{}]]", link),
             }
-            in_todo_section = false;
         }
+    }
+
+    Ok(())
+}
+
+/// Remove a task (with its nested lines) from today's TODO section
+pub fn today_remove(item: &str, config: &Config) -> Result<()> {
+    let (file_path, mut doc) = read_today_report(config)?;
+    let item = select_today_item(&doc, item, &config.daily_report.todo)?;
+
+    doc.remove(&item);
+    fs::write(&file_path, doc.render())
+        .with_context(|| format!("Failed to update daily report: {:?}", file_path))?;
+
+    println!("Removed: {}", parse_todo_item(&item.text).1);
+
+    Ok(())
+}
+
+/// Replace a task's text (keeping its checkbox), or open the editor on it
+pub fn today_edit(item: &str, text: Option<&str>, config: &Config) -> Result<()> {
+    let (file_path, mut doc) = read_today_report(config)?;
+    let item = select_today_item(&doc, item, &config.daily_report.todo)?;
+
+    let Some(text) = text else {
+        return open_editor_at_line(&file_path, &config.general.editor, item.line + 1);
+    };
+
+    let (done, old_text) = parse_todo_item(&item.text);
+    let mark = if done { "x" } else { " " };
+    doc.set_line(item.line, &format!("- [{}] {}", mark, text));
+
+    fs::write(&file_path, doc.render())
+        .with_context(|| format!("Failed to update daily report: {:?}", file_path))?;
+
+    println!("Changed: {} -> {}", old_text, text);
 
-        result.push(line.to_string());
+    Ok(())
+}
+
+/// Path and parsed content of today's daily report, which must exist
+fn read_today_report(config: &Config) -> Result<(PathBuf, Document)> {
+    let date = get_current_date(&config.general.date_format);
+    let file_path = config.inbox_dir()?.join(format!("{}-daily_report.md", date));
+
+    if !file_path.exists() {
+        anyhow::bail!("No daily report found for today ({}).", date);
+    }
+
+    let content = fs::read_to_string(&file_path)
+        .with_context(|| format!("Failed to read daily report: {:?}", file_path))?;
+
+    Ok((file_path, Document::parse(&content)))
+}
+
+/// Find an item of the `heading` section by number (1-based, as in
+/// `today list`) or by text
+fn select_today_item(doc: &Document, query: &str, heading: &str) -> Result<markdown::ListItem> {
+    let items = doc.section_items(heading);
+
+    if let Ok(n) = query.parse::<usize>() {
+        let Some(item) = n.checked_sub(1).and_then(|i| items.get(i)) else {
+            anyhow::bail!("Task {} not found ({} in {} section)", n, items.len(), heading);
+        };
+        return Ok(item.clone());
     }
 
-    // If TODO section was last section
-    if in_todo_section && !task_added {
-        result.push(task.to_string());
+    let query_lower = query.to_lowercase();
+    let matches: Vec<&markdown::ListItem> = items
+        .iter()
+        .filter(|item| parse_todo_item(&item.text).1.to_lowercase().contains(&query_lower))
+        .collect();
+
+    match matches.as_slice() {
+        [] => anyhow::bail!("No task matching '{}' in today's {} section", query, heading),
+        [item] => Ok((*item).clone()),
+        _ => {
+            let names: Vec<&str> = matches.iter().map(|item| parse_todo_item(&item.text).1).collect();
+            anyhow::bail!("'{}' matches {} tasks, use the number instead: {}", query, matches.len(), names.join(", "))
+        }
     }
+}
 
-    result.join("\n")
+/// Targets of `[[link]]` / `[[link|label]]` references
+fn wiki_links(text: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("[[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else {
+            break;
+        };
+        let target = after[..end].split('|').next().unwrap_or_default().trim();
+        let target = target.strip_suffix(".md").unwrap_or(target);
+        if !target.is_empty() {
+            links.push(target.to_string());
+        }
+        rest = &after[end + 2..];
+    }
+    links
 }
 
 pub fn weekly(config: &Config) -> Result<()> {
@@ -173,54 +429,236 @@ pub fn weekly(config: &Config) -> Result<()> {
     let iso_week = now.iso_week();
     let iso_year = iso_week.year();
     let week_number = iso_week.week();
-    let week_str = format!("W{:02}", week_number);
+    let week_label = format!("{}-W{:02}", iso_year, week_number);
 
     let weekly_dir = config.weekly_report_dir()?;
+    fs::create_dir_all(&weekly_dir)
+        .with_context(|| format!("Failed to create weekly report directory: {:?}", weekly_dir))?;
 
+    let weekly_report_path = match find_weekly_report(config, iso_year, week_number)? {
+        Some(path) => path,
+        None => create_weekly_report(config, iso_year, week_number)?,
+    };
+
+    // Start weekly session (braindump + review)
+    run_weekly_session(config, &week_label, &weekly_report_path)?;
+
+    Ok(())
+}
+
+/// Monday and Sunday of an ISO week
+fn iso_week_range(iso_year: i32, week_number: u32) -> Result<(NaiveDate, NaiveDate)> {
+    let start = NaiveDate::from_isoywd_opt(iso_year, week_number, Weekday::Mon)
+        .ok_or_else(|| anyhow::anyhow!("Invalid ISO week: {}-W{:02}", iso_year, week_number))?;
+    Ok((start, start + chrono::Duration::days(6)))
+}
+
+/// Render the configured weekly report file name for an ISO week
+fn weekly_report_filename(config: &Config, iso_year: i32, week_number: u32) -> Result<String> {
+    let (start, end) = iso_week_range(iso_year, week_number)?;
+    let iso_year_str = iso_year.to_string();
+    let week = format!("{:02}", week_number);
+    let start_date = start.format(&config.general.date_format).to_string();
+    let end_date = end.format(&config.general.date_format).to_string();
+
+    Ok(apply_replacements(
+        &config.gtd.weekly_report_filename,
+        &[
+            ("iso_year", iso_year_str.as_str()),
+            ("week", week.as_str()),
+            ("start_date", start_date.as_str()),
+            ("end_date", end_date.as_str()),
+        ],
+    ))
+}
+
+/// Locate the weekly report for an ISO week.
+///
+/// The configured file name is tried first, then any file containing the
+/// `YYYY-Www` label. Files that only carry `Www` without a year cannot be
+/// attributed to a year, so they are reported as an error rather than guessed.
+fn find_weekly_report(config: &Config, iso_year: i32, week_number: u32) -> Result<Option<PathBuf>> {
+    let weekly_dir = config.weekly_report_dir()?;
     if !weekly_dir.exists() {
-        println!("Weekly report directory not found: {}", weekly_dir.display());
-        println!("\nPlease merge the weekly report PR first.");
-        return Ok(());
+        return Ok(None);
     }
 
-    // Search for file containing this week's number
-    let mut found_file = None;
+    let expected = weekly_dir.join(weekly_report_filename(config, iso_year, week_number)?);
+    if expected.is_file() {
+        return Ok(Some(expected));
+    }
+
+    let week_str = format!("W{:02}", week_number);
+    let week_label = format!("{}-{}", iso_year, week_str);
+    let mut labeled: Vec<PathBuf> = Vec::new();
+    let mut yearless: Vec<PathBuf> = Vec::new();
+
     for entry in fs::read_dir(&weekly_dir)? {
         let entry = entry?;
         let path = entry.path();
-        if path.is_file() {
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.contains(&week_str) && name.ends_with(".md") {
-                    found_file = Some(path);
-                    break;
-                }
+        if !path.is_file() || path.extension().map(|e| e != "md").unwrap_or(true) {
+            continue;
+        }
+        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+            if name.contains(&week_label) {
+                labeled.push(path);
+            } else if contains_week_without_year(name, &week_str) {
+                yearless.push(path);
             }
         }
     }
 
-    match found_file {
-        Some(weekly_report_path) => {
-            // Start weekly session (braindump + review)
-            run_weekly_session(config, &week_str, &weekly_report_path)?;
-        }
-        None => {
-            println!("No weekly report found for {} (Week {}).", iso_year, week_number);
-            println!("\nPlease merge the weekly report PR first.");
+    labeled.sort();
+    yearless.sort();
+
+    match labeled.len() {
+        1 => return Ok(labeled.pop()),
+        0 => {}
+        _ => anyhow::bail!(
+            "Multiple weekly reports match {}:\n{}",
+            week_label,
+            format_paths(&labeled)
+        ),
+    }
+
+    if !yearless.is_empty() {
+        anyhow::bail!(
+            "Weekly report(s) for {} found without an ISO year, so it is unclear whether they belong to {}:\n{}\nRename the file to {} (or delete it) and run again.",
+            week_str,
+            iso_year,
+            format_paths(&yearless),
+            expected.file_name().and_then(|n| n.to_str()).unwrap_or_default()
+        );
+    }
+
+    Ok(None)
+}
+
+/// True if `name` contains `week_str` (e.g. "W05") not preceded by a "YYYY-" year
+fn contains_week_without_year(name: &str, week_str: &str) -> bool {
+    name.match_indices(week_str).any(|(idx, _)| {
+        // Reject longer week numbers such as "W051"
+        let followed_by_digit = name[idx + week_str.len()..]
+            .chars()
+            .next()
+            .map(|c| c.is_ascii_digit())
+            .unwrap_or(false);
+        // Compare characters, not bytes: names are often Japanese
+        let has_year = name[..idx]
+            .strip_suffix('-')
+            .map(|prefix| {
+                let year: Vec<char> = prefix.chars().rev().take(4).collect();
+                year.len() == 4 && year.iter().all(|c| c.is_ascii_digit())
+            })
+            .unwrap_or(false);
+        !followed_by_digit && !has_year
+    })
+}
+
+fn format_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|p| format!("  {}", p.display()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Create this week's report from the `weekly_report` template, filling in
+/// the week's daily reports and completed todos.
+fn create_weekly_report(config: &Config, iso_year: i32, week_number: u32) -> Result<PathBuf> {
+    let (start, end) = iso_week_range(iso_year, week_number)?;
+
+    let date_format = &config.general.date_format;
+    let start_date = start.format(date_format).to_string();
+    let end_date = end.format(date_format).to_string();
+    let iso_year_str = iso_year.to_string();
+    let week = format!("{:02}", week_number);
+
+    let daily_reports = find_daily_reports(config, start, end)?
+        .iter()
+        .map(|stem| format!("- [[{}]]", stem))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let completed_todos = find_completed_todos(config, start, end)?.join("\n");
+
+    let replacements = vec![
+        ("date", start_date.as_str()),
+        ("iso_year", iso_year_str.as_str()),
+        ("week", week.as_str()),
+        ("start_date", start_date.as_str()),
+        ("end_date", end_date.as_str()),
+        ("daily_reports", daily_reports.as_str()),
+        ("completed_todos", completed_todos.as_str()),
+    ];
+
+    let filename = weekly_report_filename(config, iso_year, week_number)?;
+    let output_path = config.weekly_report_dir()?.join(filename);
+    let template_path = config.get_template_path("weekly_report")?;
+
+    if template_path.exists() {
+        create_file_from_template(&template_path, &output_path, &replacements)?;
+    } else {
+        let content = apply_replacements(DEFAULT_WEEKLY_REPORT_TEMPLATE, &replacements);
+        fs::write(&output_path, content)
+            .with_context(|| format!("Failed to create weekly report: {:?}", output_path))?;
+    }
+
+    println!("Created weekly report: {}", output_path.display());
+
+    Ok(output_path)
+}
+
+/// Daily report file stems (e.g. "2026-10-12-daily_report") dated within [start, end]
+fn find_daily_reports(config: &Config, start: NaiveDate, end: NaiveDate) -> Result<Vec<String>> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for dir in [config.inbox_dir()?, config.archive_dir()?] {
+        if dir.exists() {
+            collect_daily_reports(&dir, &mut files)?;
         }
     }
 
+    let mut reports: Vec<String> = files
+        .iter()
+        .filter_map(|path| path.file_stem().and_then(|n| n.to_str()))
+        .filter(|stem| {
+            stem.strip_suffix("-daily_report")
+                .and_then(|date| NaiveDate::parse_from_str(date, &config.general.date_format).ok())
+                .map(|date| date >= start && date <= end)
+                .unwrap_or(false)
+        })
+        .map(|stem| stem.to_string())
+        .collect();
+
+    reports.sort();
+    reports.dedup();
+    Ok(reports)
+}
+
+fn collect_daily_reports(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_dir() {
+            collect_daily_reports(&path, files)?;
+        } else if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+            if name.ends_with("-daily_report.md") {
+                files.push(path);
+            }
+        }
+    }
     Ok(())
 }
 
-fn run_weekly_session(config: &Config, week_str: &str, weekly_report_path: &std::path::Path) -> Result<()> {
+fn run_weekly_session(config: &Config, week_label: &str, weekly_report_path: &std::path::Path) -> Result<()> {
     let date = get_current_date(&config.general.date_format);
-    let filename = format!("{}-{}-braindump.md", date, week_str);
+    let filename = format!("{}-{}-braindump.md", date, week_label);
     let inbox_dir = config.inbox_dir()?;
     let braindump_path = inbox_dir.join(&filename);
 
     // Create braindump file with header if it doesn't exist
     if !braindump_path.exists() {
-        let header = format!("# {} Braindump\n\n", week_str);
+        let header = format!("# {} Braindump\n\n", week_label);
         fs::write(&braindump_path, header)?;
     }
 
@@ -250,7 +688,7 @@ fn run_weekly_session(config: &Config, week_str: &str, weekly_report_path: &std:
         &mut item_count,
         start,
         duration_secs,
-        week_str,
+        week_label,
     );
 
     if braindump_result.is_err() {
@@ -261,11 +699,12 @@ fn run_weekly_session(config: &Config, week_str: &str, weekly_report_path: &std:
 
     // Phase 2: Review
     input.clear();
+    let review_title = format!("{} Weekly Review", week_label);
     let review_result = run_review_tui(
         &mut terminal,
         &mut sections,
         &mut input,
-        week_str,
+        &review_title,
     );
 
     // Restore terminal
@@ -279,7 +718,7 @@ fn run_weekly_session(config: &Config, week_str: &str, weekly_report_path: &std:
     fs::write(weekly_report_path, updated_content)?;
 
     // Add Obsidian-style link to weekly report
-    let braindump_link = format!("{}-{}-braindump", date, week_str);
+    let braindump_link = format!("{}-{}-braindump", date, week_label);
     add_link_to_weekly_report(weekly_report_path, &braindump_link)?;
 
     println!("\nWeekly session complete!");
@@ -294,11 +733,15 @@ fn parse_review_sections(content: &str) -> Vec<ReviewSection> {
     let mut sections = Vec::new();
     let mut current_section: Option<ReviewSection> = None;
 
-    // Sections to skip
-    let skip_patterns = ["dailyreport", "daily_report", "daily report"];
+    // Sections to skip (generated link and task lists)
+    let skip_patterns = [
+        "dailyreport", "daily_report", "daily report",
+        "weeklyreport", "weekly_report", "weekly report",
+        "completed",
+    ];
 
     for line in content.lines() {
-        if line.starts_with("## ") {
+        if let Some(heading) = line.strip_prefix("## ") {
             // Save previous section
             if let Some(section) = current_section.take() {
                 if !section.prompts.is_empty() {
@@ -306,7 +749,7 @@ fn parse_review_sections(content: &str) -> Vec<ReviewSection> {
                 }
             }
             // Start new section (skip DailyReport sections)
-            let title = line[3..].to_string();
+            let title = heading.to_string();
             let title_lower = title.to_lowercase();
             let should_skip = skip_patterns.iter().any(|p| title_lower.contains(p));
 
@@ -353,8 +796,7 @@ fn build_updated_weekly_report(original: &str, sections: &[ReviewSection]) -> St
     let mut prompt_idx = 0;
 
     for line in original.lines() {
-        if line.starts_with("## ") {
-            let title = &line[3..];
+        if let Some(title) = line.strip_prefix("## ") {
             current_section_idx = sections.iter().position(|s| s.title == title);
             prompt_idx = 0;
             result.push_str(line);
@@ -414,7 +856,7 @@ fn run_braindump_tui(
     item_count: &mut usize,
     start: Instant,
     duration_secs: u64,
-    week_str: &str,
+    week_label: &str,
 ) -> Result<()> {
     loop {
         let elapsed = start.elapsed().as_secs();
@@ -442,7 +884,7 @@ fn run_braindump_tui(
                 .split(frame.area());
 
             // Title
-            let title = Paragraph::new(format!(" {} Weekly Braindump", week_str))
+            let title = Paragraph::new(format!(" {} Weekly Braindump", week_label))
                 .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
                 .block(Block::default().borders(Borders::ALL));
             frame.render_widget(title, chunks[0]);
@@ -481,16 +923,14 @@ fn run_braindump_tui(
             if let Event::Key(key) = event::read()? {
                 if key.kind == KeyEventKind::Press {
                     match key.code {
-                        KeyCode::Enter => {
-                            if !input.is_empty() {
-                                // Save to file
-                                let mut file = OpenOptions::new()
-                                    .append(true)
-                                    .open(file_path)?;
-                                writeln!(file, "- {}", input)?;
-                                *item_count += 1;
-                                input.clear();
-                            }
+                        KeyCode::Enter if !input.is_empty() => {
+                            // Save to file
+                            let mut file = OpenOptions::new()
+                                .append(true)
+                                .open(file_path)?;
+                            writeln!(file, "- {}", input)?;
+                            *item_count += 1;
+                            input.clear();
                         }
                         KeyCode::Char(c) => {
                             input.push(c);
@@ -515,7 +955,7 @@ fn run_review_tui(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     sections: &mut [ReviewSection],
     input: &mut String,
-    week_str: &str,
+    review_title: &str,
 ) -> Result<()> {
     if sections.is_empty() {
         return Ok(());
@@ -544,7 +984,7 @@ fn run_review_tui(
                 .split(frame.area());
 
             // Title
-            let title = Paragraph::new(format!(" {} Weekly Review", week_str))
+            let title = Paragraph::new(format!(" {}", review_title))
                 .style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD))
                 .block(Block::default().borders(Borders::ALL));
             frame.render_widget(title, chunks[0]);
@@ -617,12 +1057,10 @@ fn run_review_tui(
             if let Event::Key(key) = event::read()? {
                 if key.kind == KeyEventKind::Press {
                     match key.code {
-                        KeyCode::Enter => {
+                        KeyCode::Enter if !input.is_empty() => {
                             // Add response to current prompt (stay on same prompt)
-                            if !input.is_empty() {
-                                sections[section_idx].prompts[prompt_idx].responses.push(input.clone());
-                                input.clear();
-                            }
+                            sections[section_idx].prompts[prompt_idx].responses.push(input.clone());
+                            input.clear();
                         }
                         KeyCode::Tab => {
                             // Move to next prompt
@@ -660,7 +1098,566 @@ fn run_review_tui(
     Ok(())
 }
 
-pub fn monthly(_config: &Config) -> Result<()> {
-    println!("GTD monthly review command - not yet implemented");
+pub fn monthly(config: &Config) -> Result<()> {
+    let today = Local::now().date_naive();
+    let month_str = today.format("%Y-%m").to_string();
+
+    let monthly_dir = config.monthly_report_dir()?;
+    fs::create_dir_all(&monthly_dir)
+        .with_context(|| format!("Failed to create monthly report directory: {:?}", monthly_dir))?;
+
+    let month_start = NaiveDate::from_ymd_opt(today.year(), today.month(), 1)
+        .ok_or_else(|| anyhow::anyhow!("Invalid month: {}", month_str))?;
+    let month_end = month_start
+        .checked_add_months(Months::new(1))
+        .and_then(|d| d.pred_opt())
+        .ok_or_else(|| anyhow::anyhow!("Invalid month: {}", month_str))?;
+
+    let weekly_reports = find_weekly_reports_for_month(config, today.year(), today.month())?;
+    let completed = find_completed_todos(config, month_start, month_end)?;
+
+    // Search for file containing this month (e.g. 2026-10)
+    let mut found_file = None;
+    for entry in fs::read_dir(&monthly_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_file() {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                if name.contains(&month_str) && name.ends_with(".md") {
+                    found_file = Some(path);
+                    break;
+                }
+            }
+        }
+    }
+
+    let monthly_report_path = match found_file {
+        Some(path) => path,
+        None => {
+            let path = monthly_dir.join(format!("{}-monthly_report.md", month_str));
+            fs::write(&path, default_monthly_report(&month_str))
+                .with_context(|| format!("Failed to create monthly report: {:?}", path))?;
+            println!("Created monthly report: {}", path.display());
+            path
+        }
+    };
+
+    // Refresh the completed task list with what has been archived so far
+    let content = fs::read_to_string(&monthly_report_path)
+        .with_context(|| format!("Failed to read monthly report: {:?}", monthly_report_path))?;
+    let content = replace_completed_tasks(&content, &completed);
+    fs::write(&monthly_report_path, &content)?;
+
+    run_monthly_session(&month_str, &monthly_report_path)?;
+
+    for weekly_report in &weekly_reports {
+        add_link_to_weekly_report(&monthly_report_path, weekly_report)?;
+    }
+
+    println!("\nMonthly review complete!");
+    println!("Weekly reports linked: {}", weekly_reports.len());
+    println!("Completed tasks: {}", completed.len());
+    println!("Monthly report updated: {}", monthly_report_path.display());
+
+    Ok(())
+}
+
+fn run_monthly_session(month_str: &str, monthly_report_path: &Path) -> Result<()> {
+    let monthly_content = fs::read_to_string(monthly_report_path)?;
+    let mut sections = parse_review_sections(&monthly_content);
+
+    if sections.is_empty() {
+        println!("No review sections found in: {}", monthly_report_path.display());
+        return Ok(());
+    }
+
+    // Setup terminal
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let mut input = String::new();
+    let review_title = format!("{} Monthly Review", month_str);
+    let review_result = run_review_tui(
+        &mut terminal,
+        &mut sections,
+        &mut input,
+        &review_title,
+    );
+
+    // Restore terminal
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+
+    review_result?;
+
+    let updated_content = build_updated_weekly_report(&monthly_content, &sections);
+    fs::write(monthly_report_path, updated_content)?;
+
+    Ok(())
+}
+
+fn default_monthly_report(month_str: &str) -> String {
+    format!(
+        "---\ndate: {}\n---\n\n# {} Monthly Report\n\n\
+## Completed Tasks\n\n\
+## Review\n\
+- 今月うまくいったことは？\n\
+- うまくいかなかったことは？\n\
+- 来月フォーカスすることは？\n\n\
+## Projects\n\
+- 進行中のプロジェクトは目標に沿っているか？\n\
+- 開始・保留・中止すべきプロジェクトは？\n\n\
+## Weekly Reports\n",
+        month_str, month_str
+    )
+}
+
+/// Weekly report file stems whose ISO week overlaps the given month
+fn find_weekly_reports_for_month(config: &Config, year: i32, month: u32) -> Result<Vec<String>> {
+    let mut weeks: Vec<(i32, u32)> = Vec::new();
+    let mut day = NaiveDate::from_ymd_opt(year, month, 1)
+        .ok_or_else(|| anyhow::anyhow!("Invalid month: {}-{}", year, month))?;
+    while day.month() == month {
+        let iso_week = day.iso_week();
+        let week = (iso_week.year(), iso_week.week());
+        if !weeks.contains(&week) {
+            weeks.push(week);
+        }
+        match day.succ_opt() {
+            Some(next) => day = next,
+            None => break,
+        }
+    }
+
+    let mut reports = Vec::new();
+    for (iso_year, week_number) in weeks {
+        // An unclear match for one week should not abort the whole review
+        let path = match find_weekly_report(config, iso_year, week_number) {
+            Ok(path) => path,
+            Err(e) => {
+                eprintln!("Warning: skipping week {}-W{:02}: {}", iso_year, week_number, e);
+                None
+            }
+        };
+        if let Some(stem) = path.as_ref().and_then(|p| p.file_stem()).and_then(|n| n.to_str()) {
+            reports.push(stem.to_string());
+        }
+    }
+
+    Ok(reports)
+}
+
+/// Archived todos completed within [start, end], as checked list items
+fn find_completed_todos(config: &Config, start: NaiveDate, end: NaiveDate) -> Result<Vec<String>> {
+    let archive_dir = config.archive_dir()?;
+    let mut todos: Vec<TodoItem> = Vec::new();
+    if archive_dir.exists() {
+        collect_all_todos_recursive(&archive_dir, &mut todos)?;
+    }
+
+    todos.retain(|t| {
+        t.status == "done"
+            && NaiveDate::parse_from_str(&t.completed, &config.general.date_format)
+                .map(|d| d >= start && d <= end)
+                .unwrap_or(false)
+    });
+    todos.sort_by_key(|t| NaiveDate::parse_from_str(&t.completed, &config.general.date_format).ok());
+
+    Ok(todos
+        .iter()
+        .map(|t| {
+            let stem = t.path.file_stem().and_then(|n| n.to_str()).unwrap_or("");
+            format!("- [x] {} [[{}]] ({})", t.title, stem, t.completed)
+        })
+        .collect())
+}
+
+/// Replace the list items (with their nested lines) of the first `## `
+/// section whose title mentions "completed". Other lines are kept as-is.
+fn replace_completed_tasks(content: &str, items: &[String]) -> String {
+    let mut doc = Document::parse(content);
+    let heading = match doc.find_section(|h| h.to_lowercase().contains("completed")) {
+        Some(section) => {
+            for item in doc.items(section).iter().rev() {
+                doc.remove(item);
+            }
+            doc.lines(section.heading, section.heading + 1)[0][3..].trim().to_string()
+        }
+        None if items.is_empty() => return content.to_string(),
+        None => "Completed Tasks".to_string(),
+    };
+    doc.append(&heading, &items.join("\n"));
+    doc.render()
+}
+
+/// Clarify choices offered for each inbox item
+#[derive(Debug, Clone, Copy)]
+enum ProcessChoice {
+    Next,
+    Waiting,
+    Done,
+    Someday,
+    Reference,
+    Project,
+    Trash,
+}
+
+const PROCESS_CHOICES: [(char, ProcessChoice); 7] = [
+    ('n', ProcessChoice::Next),
+    ('w', ProcessChoice::Waiting),
+    ('d', ProcessChoice::Done),
+    ('s', ProcessChoice::Someday),
+    ('r', ProcessChoice::Reference),
+    ('p', ProcessChoice::Project),
+    ('t', ProcessChoice::Trash),
+];
+
+impl ProcessChoice {
+    fn label(self) -> &'static str {
+        match self {
+            ProcessChoice::Next => "Next action",
+            ProcessChoice::Waiting => "Delegate (waiting)",
+            ProcessChoice::Done => "Do it now (done)",
+            ProcessChoice::Someday => "Someday / maybe",
+            ProcessChoice::Reference => "Reference (archive)",
+            ProcessChoice::Project => "Promote to new project",
+            ProcessChoice::Trash => "Trash",
+        }
+    }
+
+    fn status(self) -> &'static str {
+        match self {
+            ProcessChoice::Next | ProcessChoice::Project => "next",
+            ProcessChoice::Waiting => "waiting",
+            ProcessChoice::Done => "done",
+            ProcessChoice::Someday => "someday",
+            ProcessChoice::Reference => "reference",
+            ProcessChoice::Trash => "trash",
+        }
+    }
+}
+
+pub fn process(config: &Config) -> Result<()> {
+    let inbox_dir = config.inbox_dir()?;
+    let mut notes = Vec::new();
+    collect_notes(&inbox_dir, false, &mut notes)?;
+
+    // Daily reports live in the inbox but are not items to clarify,
+    // and deferred items wait for their review_date
+    let today = Local::now().date_naive();
+    let date_format = &config.general.date_format;
+    notes.retain(|n| !n.stem().ends_with("daily_report") && !is_deferred(n, today, date_format));
+    notes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    if notes.is_empty() {
+        println!("Inbox is empty.");
+        return Ok(());
+    }
+
+    // Setup terminal
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let process_result = run_process_tui(&mut terminal, &notes, config);
+
+    // Restore terminal
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+
+    let processed = process_result?;
+
+    if processed.is_empty() {
+        println!("No items processed.");
+        return Ok(());
+    }
+
+    for (title, outcome) in &processed {
+        println!("{} -> {}", title, outcome);
+    }
+    println!("\nProcessed {} of {} inbox item(s)", processed.len(), notes.len());
+
+    Ok(())
+}
+
+/// Tickler: deferred todos whose `review_date:` has arrived, optionally moved
+/// back to the inbox with the date cleared
+pub fn tickler(move_to_inbox: bool, config: &Config) -> Result<()> {
+    let root_dir = config.root_dir()?;
+    let date_format = &config.general.date_format;
+    let today = Local::now().date_naive();
+
+    let mut todos = collect_active_todos(config)?;
+    let someday_dir = config.someday_dir()?;
+    if someday_dir.exists() {
+        collect_todos(&someday_dir, &mut todos)?;
+    }
+
+    let waiting = todos
+        .iter()
+        .filter(|t| is_deferred(&t.note, today, date_format))
+        .count();
+    let mut arrived: Vec<TodoItem> = todos
+        .into_iter()
+        .filter(|t| t.note.frontmatter.review_date(date_format).map(|d| d <= today).unwrap_or(false))
+        .collect();
+    arrived.sort_by_key(|t| t.note.frontmatter.review_date(date_format));
+
+    if arrived.is_empty() {
+        println!("Nothing in the tickler for today ({} item(s) deferred to a later date).", waiting);
+        return Ok(());
+    }
+
+    for (i, todo) in arrived.iter().enumerate() {
+        let review = todo.note.frontmatter.get_str("review_date").unwrap_or_default();
+        let display_path = todo.path.strip_prefix(&root_dir)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| todo.path.display().to_string());
+        println!("{}: {} - {} [{}]", i + 1, review, todo.title, todo.status);
+        println!("   {}", display_path);
+    }
+    println!("\n{} item(s) ready, {} still deferred", arrived.len(), waiting);
+
+    if !move_to_inbox {
+        println!("Run `snail gtd tickler --move` to move them back to the inbox.");
+        return Ok(());
+    }
+
+    let inbox_dir = config.inbox_dir()?;
+    println!();
+    for todo in arrived {
+        let mut note = todo.note;
+        note.frontmatter.set("review_date", "");
+        let dest = move_note(note, "inbox", &inbox_dir)?;
+        println!("Moved to inbox: {}", dest.display());
+    }
+
     Ok(())
 }
+
+/// Walk through inbox notes; returns (title, outcome) for each processed item
+fn run_process_tui(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    notes: &[Note],
+    config: &Config,
+) -> Result<Vec<(String, String)>> {
+    let root_dir = config.root_dir()?;
+    let mut processed: Vec<(String, String)> = Vec::new();
+    let mut idx = 0;
+    // Some(name) while asking for the new project's name
+    let mut project_name: Option<String> = None;
+    let mut message = String::new();
+
+    while idx < notes.len() {
+        let note = &notes[idx];
+        let title = note.title();
+        let display_path = note.path.strip_prefix(&root_dir)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| note.path.display().to_string());
+
+        terminal.draw(|frame| {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(2)
+                .constraints([
+                    Constraint::Length(3),  // Title
+                    Constraint::Length(4),  // Item
+                    Constraint::Min(3),     // Preview
+                    Constraint::Length(9),  // Choices / project name
+                    Constraint::Length(2),  // Help
+                ])
+                .split(frame.area());
+
+            // Title
+            let header = Paragraph::new(format!(" Inbox Processing  {}/{}", idx + 1, notes.len()))
+                .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+                .block(Block::default().borders(Borders::ALL));
+            frame.render_widget(header, chunks[0]);
+
+            // Current item
+            let item = Paragraph::new(vec![
+                Line::from(Span::styled(format!(" {}", title), Style::default().fg(Color::White).add_modifier(Modifier::BOLD))),
+                Line::from(Span::styled(format!(" {}", display_path), Style::default().fg(Color::DarkGray))),
+            ])
+            .block(Block::default().title(" Item ").borders(Borders::ALL));
+            frame.render_widget(item, chunks[1]);
+
+            // Body preview
+            let preview: Vec<Line> = note.body
+                .lines()
+                .skip_while(|l| l.trim().is_empty())
+                .map(|l| Line::from(format!(" {}", l)))
+                .collect();
+            let preview = Paragraph::new(preview)
+                .block(Block::default().title(" Preview ").borders(Borders::ALL));
+            frame.render_widget(preview, chunks[2]);
+
+            // Choices, or the project name input
+            if let Some(ref name) = project_name {
+                let input_widget = Paragraph::new(format!(" > {}_", name))
+                    .style(Style::default().fg(Color::White))
+                    .block(Block::default()
+                        .title(" New project name ")
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Cyan)));
+                frame.render_widget(input_widget, chunks[3]);
+            } else {
+                let items: Vec<ListItem> = PROCESS_CHOICES.iter().map(|(key, choice)| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!(" [{}] ", key), Style::default().fg(Color::Yellow)),
+                        Span::raw(choice.label()),
+                    ]))
+                }).collect();
+                let list = List::new(items)
+                    .block(Block::default().title(" What is it? ").borders(Borders::ALL));
+                frame.render_widget(list, chunks[3]);
+            }
+
+            // Help text, or the last error
+            let help = if !message.is_empty() {
+                Paragraph::new(format!(" {}", message)).style(Style::default().fg(Color::Red))
+            } else if project_name.is_some() {
+                Paragraph::new(" Enter: Create project | Esc: Back")
+                    .style(Style::default().fg(Color::DarkGray))
+            } else {
+                Paragraph::new(" Space: Skip | Esc/q: Quit")
+                    .style(Style::default().fg(Color::DarkGray))
+            };
+            frame.render_widget(help, chunks[4]);
+        })?;
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        // Project name input
+        if let Some(ref mut name) = project_name {
+            match key.code {
+                KeyCode::Enter if !name.trim().is_empty() => {
+                    match promote_note(note.clone(), name, config) {
+                        Ok(promoted) => {
+                            let dest = &promoted.dest_path;
+                            let dest = dest.strip_prefix(&root_dir)
+                                .map(|p| p.display().to_string())
+                                .unwrap_or_else(|_| dest.display().to_string());
+                            processed.push((title, format!("project {} ({})", promoted.name, dest)));
+                            idx += 1;
+                            message.clear();
+                        }
+                        Err(e) => message = e.to_string(),
+                    }
+                    project_name = None;
+                }
+                KeyCode::Char(c) => name.push(c),
+                KeyCode::Backspace => {
+                    name.pop();
+                }
+                KeyCode::Esc => project_name = None,
+                _ => {}
+            }
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => break,
+            KeyCode::Char(' ') | KeyCode::Right => {
+                idx += 1;
+                message.clear();
+            }
+            KeyCode::Char(c) => {
+                let Some(&(_, choice)) = PROCESS_CHOICES.iter().find(|(k, _)| *k == c) else {
+                    continue;
+                };
+                if let ProcessChoice::Project = choice {
+                    project_name = Some(sanitize_filename(&title));
+                    continue;
+                }
+
+                let result = match choice {
+                    ProcessChoice::Done => archive_done(note.clone(), config).map(|(dest, _)| dest),
+                    ProcessChoice::Reference | ProcessChoice::Trash => {
+                        archive_note(note.clone(), choice.status(), config)
+                    }
+                    _ => config.status_dir(choice.status())
+                        .and_then(|dir| move_note(note.clone(), choice.status(), &dir)),
+                };
+                match result {
+                    Ok(dest) => {
+                        let dest = dest.strip_prefix(&root_dir)
+                            .map(|p| p.display().to_string())
+                            .unwrap_or_else(|_| dest.display().to_string());
+                        processed.push((title, format!("{} ({})", choice.status(), dest)));
+                        idx += 1;
+                        message.clear();
+                    }
+                    Err(e) => message = e.to_string(),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(processed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn week_without_year_handles_japanese_names() {
+        assert!(contains_week_without_year("週報-W42.md", "W42"));
+        assert!(contains_week_without_year("W42-週報.md", "W42"));
+        assert!(!contains_week_without_year("週報2025-W42.md", "W42"));
+        assert!(!contains_week_without_year("2025-W42-weekly_report.md", "W42"));
+        assert!(!contains_week_without_year("週報-W421.md", "W42"));
+        assert!(!contains_week_without_year("年報-W41.md", "W42"));
+    }
+
+    #[test]
+    fn completed_tasks_are_replaced_with_nested_lines() {
+        let content = "# 2026-10 Monthly Report\n\n## Completed Tasks\n- [x] old\n  - note\n\n```\n- kept in fence\n```\n\n## Review\n- question\n";
+        let items = vec!["- [x] new".to_string()];
+        assert_eq!(
+            replace_completed_tasks(content, &items),
+            "# 2026-10 Monthly Report\n\n## Completed Tasks\n\n```\n- kept in fence\n```\n- [x] new\n\n## Review\n- question\n"
+        );
+    }
+
+    #[test]
+    fn completed_tasks_refresh_is_stable() {
+        let items = vec!["- [x] a".to_string(), "- [x] b".to_string()];
+        let once = replace_completed_tasks(&default_monthly_report("2026-10"), &items);
+        assert!(once.contains("## Completed Tasks\n- [x] a\n- [x] b\n\n## Review"));
+        assert_eq!(replace_completed_tasks(&once, &items), once);
+    }
+
+    #[test]
+    fn yearless_weekly_report_does_not_abort_the_month() {
+        let root = std::env::temp_dir().join(format!("snail-gtd-monthly-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let mut config = Config::default();
+        config.general.root_dir = root.display().to_string();
+        let weekly_dir = config.weekly_report_dir().unwrap();
+        fs::create_dir_all(&weekly_dir).unwrap();
+        fs::write(weekly_dir.join("週報-W41.md"), "").unwrap();
+        fs::write(weekly_dir.join(weekly_report_filename(&config, 2026, 42).unwrap()), "").unwrap();
+
+        let reports = find_weekly_reports_for_month(&config, 2026, 10).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].contains("2026-W42"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
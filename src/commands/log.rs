@@ -0,0 +1,70 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use crate::commands::gtd::{create_daily_report, print_created_report};
+use crate::commands::todo::resolve_todo_file;
+use crate::config::Config;
+use crate::markdown::Document;
+use crate::note::Note;
+
+/// Append `- HH:MM message` to the log section of today's daily report,
+/// and optionally to a todo's `## ログ (Log)` section. Without a message,
+/// each non-empty line of stdin becomes an entry.
+pub fn log(message: Option<&str>, todo: Option<&str>, config: &Config) -> Result<()> {
+    let input = match message {
+        Some(message) => message.to_string(),
+        None => {
+            if io::stdin().is_terminal() {
+                anyhow::bail!("No message given (pass it as an argument or pipe it in)");
+            }
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            input
+        }
+    };
+    let messages: Vec<&str> = input.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    if messages.is_empty() {
+        anyhow::bail!("Empty log message");
+    }
+
+    // Resolve the todo first so a bad name writes nothing
+    let mut todo = todo
+        .map(|file| resolve_todo_file(file, config).and_then(|path| Note::load(&path)))
+        .transpose()?;
+
+    let now = Local::now();
+    let time = now.format("%H:%M").to_string();
+    let date = now.date_naive().format(&config.general.date_format).to_string();
+
+    let file_path = config.inbox_dir()?.join(format!("{}-daily_report.md", date));
+    if !file_path.exists() {
+        let carry_over = create_daily_report(&file_path, config)?;
+        print_created_report(&file_path, carry_over);
+    }
+
+    let content = fs::read_to_string(&file_path)
+        .with_context(|| format!("Failed to read daily report: {:?}", file_path))?;
+    let mut doc = Document::parse(&content);
+    for message in &messages {
+        let entry = match todo {
+            Some(ref note) => format!("- {} {} ([[{}]])", time, message, note.stem()),
+            None => format!("- {} {}", time, message),
+        };
+        doc.append(&config.daily_report.log, &entry);
+    }
+    fs::write(&file_path, doc.render())
+        .with_context(|| format!("Failed to update daily report: {:?}", file_path))?;
+
+    println!("Logged to daily report: {}", file_path.display());
+
+    if let Some(ref mut note) = todo {
+        for message in &messages {
+            note.append_log(&format!("- {} {}: {}", date, time, message));
+        }
+        note.save()?;
+        println!("Logged to todo: {}", note.path.display());
+    }
+
+    Ok(())
+}
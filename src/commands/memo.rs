@@ -1,9 +1,13 @@
 use anyhow::Result;
+use std::collections::HashSet;
 use std::fs;
-use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use crate::cli::{OutputArgs, OutputFormat};
 use crate::config::Config;
-use crate::utils::{create_file_from_base_and_snip, create_file_from_template, get_current_date, open_editor, sanitize_filename};
+use crate::note::{collect_notes, Note};
+use crate::output::{print_records, Record};
+use crate::query::{Query, QueryContext};
+use crate::utils::{create_file_from_base_and_snip, create_file_from_template, get_current_date, open_editor, open_editor_at_line, prompt_selection, sanitize_filename};
 
 pub fn new(title: &str, no_edit: bool, config: &Config) -> Result<()> {
     let date = get_current_date(&config.general.date_format);
@@ -42,9 +46,11 @@ pub fn new(title: &str, no_edit: bool, config: &Config) -> Result<()> {
     Ok(())
 }
 
-pub fn list(config: &Config) -> Result<()> {
+pub fn list(filters: &[String], archive: bool, output: &OutputArgs, config: &Config) -> Result<()> {
     let mut memos: Vec<MemoItem> = Vec::new();
     let root_dir = config.root_dir()?;
+    let query = Query::parse_all(filters)?;
+    let ctx = QueryContext::from_config(config);
 
     // Search in INBOX, NEXTACTION, and project directories
     let search_dirs = vec![
@@ -64,14 +70,41 @@ pub fn list(config: &Config) -> Result<()> {
         collect_memos_recursive(&project_dir, &mut memos)?;
     }
 
-    if memos.is_empty() {
-        println!("No memos found.");
-        return Ok(());
+    // Archived memos and reference material on request
+    let archive_dir = config.archive_dir()?;
+    if archive && archive_dir.exists() {
+        let mut notes = Vec::new();
+        collect_notes(&archive_dir, true, &mut notes)?;
+        memos.extend(notes.into_iter().filter_map(MemoItem::from_archived_note));
+    }
+
+    // Apply filters
+    if let Some(ref query) = query {
+        memos.retain(|m| query.matches(&m.note, &ctx));
     }
 
     // Sort by created date (newest first)
     memos.sort_by(|a, b| b.created.cmp(&a.created));
 
+    if output.format != OutputFormat::Text {
+        let records: Vec<Record> = memos
+            .iter()
+            .map(|m| {
+                Record::new()
+                    .field("title", m.title.as_str())
+                    .field("created", m.created.as_str())
+                    .paths(&m.path, &root_dir)
+                    .frontmatter(&m.note.frontmatter)
+            })
+            .collect();
+        return print_records(output.format, &records);
+    }
+
+    if memos.is_empty() {
+        println!("No memos found.");
+        return Ok(());
+    }
+
     // Display memos
     for (i, memo) in memos.iter().enumerate() {
         let display_path = memo.path.strip_prefix(&root_dir)
@@ -83,21 +116,9 @@ pub fn list(config: &Config) -> Result<()> {
 
     println!("\nTotal: {} memo(s)", memos.len());
 
-    // Prompt for selection
-    print!("Open file (1-{}, or Enter to skip): ", memos.len());
-    io::stdout().flush()?;
-
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-    let input = input.trim();
-
-    if !input.is_empty() {
-        if let Ok(selection) = input.parse::<usize>() {
-            if selection >= 1 && selection <= memos.len() {
-                open_editor(&memos[selection - 1].path, &config.general.editor)?;
-            } else {
-                println!("Invalid selection: {}", selection);
-            }
+    if output.interactive() {
+        if let Some(selection) = prompt_selection("Open file", memos.len())? {
+            open_editor(&memos[selection].path, &config.general.editor)?;
         }
     }
 
@@ -108,107 +129,391 @@ struct MemoItem {
     title: String,
     created: String,
     path: PathBuf,
+    note: Note,
 }
 
-fn parse_frontmatter(content: &str) -> Option<(String, String)> {
-    let lines: Vec<&str> = content.lines().collect();
+impl MemoItem {
+    /// A note is a memo when it has frontmatter without an active task status
+    fn from_note(note: Note) -> Option<Self> {
+        if !note.frontmatter.is_present() || note.frontmatter.status().is_some() {
+            return None;
+        }
+        Some(Self::new(note))
+    }
 
-    if lines.is_empty() || lines[0] != "---" {
-        return None;
+    /// In the archive, notes filed as `status: reference` count as memos too
+    fn from_archived_note(note: Note) -> Option<Self> {
+        if note.frontmatter.status().as_deref() == Some("reference") {
+            return Some(Self::new(note));
+        }
+        Self::from_note(note)
     }
 
-    let mut end_index = None;
-    for (i, line) in lines.iter().enumerate().skip(1) {
-        if *line == "---" {
-            end_index = Some(i);
-            break;
+    fn new(note: Note) -> Self {
+        Self {
+            title: note.title(),
+            created: note.frontmatter.created().unwrap_or_default(),
+            path: note.path.clone(),
+            note,
         }
     }
+}
+
+fn collect_memos(dir: &Path, memos: &mut Vec<MemoItem>) -> Result<()> {
+    let mut notes = Vec::new();
+    collect_notes(dir, false, &mut notes)?;
+    memos.extend(notes.into_iter().filter_map(MemoItem::from_note));
+    Ok(())
+}
+
+fn collect_memos_recursive(dir: &Path, memos: &mut Vec<MemoItem>) -> Result<()> {
+    let mut notes = Vec::new();
+    collect_notes(dir, true, &mut notes)?;
+    memos.extend(notes.into_iter().filter_map(MemoItem::from_note));
+    Ok(())
+}
+
+pub fn search(keyword: &str, output: &OutputArgs, config: &Config) -> Result<()> {
+    let terms: Vec<String> = keyword
+        .split_whitespace()
+        .map(normalize_for_search)
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    if terms.is_empty() {
+        println!("Please specify a search keyword.");
+        return Ok(());
+    }
+
+    let root_dir = config.root_dir()?;
+    let mut files: Vec<PathBuf> = Vec::new();
+
+    // Search in INBOX, NEXTACTION, and project directories
+    let search_dirs = vec![
+        config.inbox_dir()?,
+        config.next_dir()?,
+    ];
 
-    let end_index = end_index?;
+    for dir in search_dirs {
+        if dir.exists() {
+            collect_markdown_files(&dir, false, &mut files)?;
+        }
+    }
 
-    let mut status = String::new();
-    let mut created = String::new();
+    let project_dir = config.project_dir()?;
+    if project_dir.exists() {
+        collect_markdown_files(&project_dir, true, &mut files)?;
+    }
 
-    for line in &lines[1..end_index] {
-        if let Some((key, value)) = line.split_once(':') {
-            let key = key.trim();
-            let value = value.trim();
-            match key {
-                "status" => status = value.to_string(),
-                "created" | "date" => created = value.to_string(),
-                _ => {}
+    let mut hits: Vec<SearchHit> = Vec::new();
+    for path in files {
+        if let Ok(content) = fs::read_to_string(&path) {
+            if let Some(hit) = score_note(&content, &terms) {
+                hits.push(SearchHit { path, ..hit });
             }
         }
     }
 
-    Some((status, created))
-}
+    // Highest score first, ties broken by title
+    hits.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.title.cmp(&b.title))
+    });
+
+    if output.format != OutputFormat::Text {
+        let records: Vec<Record> = hits
+            .iter()
+            .map(|hit| {
+                Record::new()
+                    .field("title", hit.title.as_str())
+                    .field("score", hit.score)
+                    .field("line", hit.line)
+                    .field("snippet", hit.snippet.as_str())
+                    .paths(&hit.path, &root_dir)
+            })
+            .collect();
+        return print_records(output.format, &records);
+    }
+
+    if hits.is_empty() {
+        println!("No memos matched: {}", keyword);
+        return Ok(());
+    }
+
+    // Display hits
+    for (i, hit) in hits.iter().enumerate() {
+        let display_path = hit.path.strip_prefix(&root_dir)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| hit.path.display().to_string());
+        println!("{}: {} (score: {:.1})", i + 1, hit.title, hit.score);
+        println!("   {}:{}", display_path, hit.line);
+        println!("   {}", hit.snippet);
+    }
+
+    println!("\nTotal: {} hit(s)", hits.len());
 
-fn extract_title(content: &str) -> String {
-    for line in content.lines() {
-        if line.starts_with("# ") {
-            return line[2..].trim().to_string();
+    if output.interactive() {
+        if let Some(selection) = prompt_selection("Open file", hits.len())? {
+            let hit = &hits[selection];
+            open_editor_at_line(&hit.path, &config.general.editor, hit.line)?;
         }
     }
-    String::new()
+
+    Ok(())
 }
 
-fn collect_memos(dir: &Path, memos: &mut Vec<MemoItem>) -> Result<()> {
+/// Minimum share of a term's character bigrams that must appear in a line
+/// for it to count as a partial (fuzzy) match.
+const MIN_NGRAM_RATIO: f64 = 0.6;
+
+const TITLE_WEIGHT: f64 = 3.0;
+const FRONTMATTER_WEIGHT: f64 = 2.0;
+const BODY_WEIGHT: f64 = 1.0;
+
+const SNIPPET_WIDTH: usize = 60;
+
+struct SearchHit {
+    title: String,
+    score: f64,
+    line: usize,
+    snippet: String,
+    path: PathBuf,
+}
+
+fn collect_markdown_files(dir: &Path, recursive: bool, files: &mut Vec<PathBuf>) -> Result<()> {
     for entry in fs::read_dir(dir)? {
         let entry = entry?;
         let path = entry.path();
 
-        if path.is_file() && path.extension().map(|e| e == "md").unwrap_or(false) {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if let Some((status, created)) = parse_frontmatter(&content) {
-                    // memo = status is empty (no active task status)
-                    if status.is_empty() {
-                        let title = extract_title(&content);
-                        memos.push(MemoItem {
-                            title,
-                            created,
-                            path,
-                        });
-                    }
-                }
+        if path.is_dir() {
+            if recursive {
+                collect_markdown_files(&path, recursive, files)?;
             }
+        } else if path.is_file() && path.extension().map(|e| e == "md").unwrap_or(false) {
+            files.push(path);
         }
     }
     Ok(())
 }
 
-fn collect_memos_recursive(dir: &Path, memos: &mut Vec<MemoItem>) -> Result<()> {
-    if !dir.is_dir() {
-        return Ok(());
+/// Lowercase and fold full-width ASCII (e.g. "ＳＮＡＩＬ") and the ideographic
+/// space to their half-width forms so that Japanese notes match ASCII queries.
+fn normalize_for_search(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\u{3000}' => ' ',
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            _ => c,
+        })
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// Character bigrams of a string, ignoring whitespace. Used instead of word
+/// tokenization because Japanese text has no spaces between words.
+fn char_bigrams(s: &str) -> HashSet<(char, char)> {
+    let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+/// How strongly `term` matches `line` (both normalized): 1.0 for a substring
+/// match, otherwise the share of the term's bigrams found in the line.
+fn match_strength(line: &str, line_bigrams: &HashSet<(char, char)>, term: &str) -> f64 {
+    if line.contains(term) {
+        return 1.0;
     }
 
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
+    let term_bigrams = char_bigrams(term);
+    if term_bigrams.is_empty() {
+        return 0.0;
+    }
 
-        if path.is_dir() {
-            collect_memos_recursive(&path, memos)?;
-        } else if path.is_file() && path.extension().map(|e| e == "md").unwrap_or(false) {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if let Some((status, created)) = parse_frontmatter(&content) {
-                    if status.is_empty() {
-                        let title = extract_title(&content);
-                        memos.push(MemoItem {
-                            title,
-                            created,
-                            path,
-                        });
-                    }
-                }
+    let shared = term_bigrams.iter().filter(|b| line_bigrams.contains(b)).count();
+    let ratio = shared as f64 / term_bigrams.len() as f64;
+    if ratio >= MIN_NGRAM_RATIO {
+        ratio
+    } else {
+        0.0
+    }
+}
+
+fn score_note(content: &str, terms: &[String]) -> Option<SearchHit> {
+    let lines: Vec<&str> = content.lines().collect();
+    let frontmatter_end = if lines.first() == Some(&"---") {
+        lines.iter().skip(1).position(|l| *l == "---").map(|i| i + 1)
+    } else {
+        None
+    };
+
+    let mut matched_terms = vec![false; terms.len()];
+    let mut total_score = 0.0;
+    let mut best: Option<(f64, usize)> = None;
+    let mut title = String::new();
+    let mut frontmatter_title = String::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let in_frontmatter = frontmatter_end.map(|end| i <= end).unwrap_or(false);
+        if in_frontmatter && (i == 0 || Some(i) == frontmatter_end) {
+            continue;
+        }
+
+        let is_title = if in_frontmatter {
+            let is_title_key = line.starts_with("title:");
+            if is_title_key {
+                frontmatter_title = line["title:".len()..].trim().trim_matches('"').to_string();
+            }
+            is_title_key
+        } else if title.is_empty() {
+            if let Some(heading) = line.strip_prefix("# ") {
+                title = heading.trim().to_string();
+                true
+            } else {
+                false
+            }
+        } else {
+            false
+        };
+
+        let weight = if is_title {
+            TITLE_WEIGHT
+        } else if in_frontmatter {
+            FRONTMATTER_WEIGHT
+        } else {
+            BODY_WEIGHT
+        };
+
+        let normalized = normalize_for_search(line);
+        let line_bigrams = char_bigrams(&normalized);
+        let mut line_score = 0.0;
+
+        for (t, term) in terms.iter().enumerate() {
+            let strength = match_strength(&normalized, &line_bigrams, term);
+            if strength > 0.0 {
+                matched_terms[t] = true;
+                line_score += strength * weight;
+            }
+        }
+
+        if line_score > 0.0 {
+            total_score += line_score;
+            if best.map(|(s, _)| line_score > s).unwrap_or(true) {
+                best = Some((line_score, i));
             }
         }
     }
 
-    Ok(())
+    // Every term has to appear somewhere in the note
+    if !matched_terms.iter().all(|m| *m) {
+        return None;
+    }
+
+    let (_, best_index) = best?;
+    if title.is_empty() {
+        title = frontmatter_title;
+    }
+
+    Some(SearchHit {
+        title,
+        score: total_score,
+        line: best_index + 1,
+        snippet: make_snippet(lines[best_index], terms),
+        path: PathBuf::new(),
+    })
 }
 
-pub fn search(_keyword: &str, _config: &Config) -> Result<()> {
-    println!("Memo search command - not yet implemented");
-    Ok(())
+/// Trim a matching line to roughly `SNIPPET_WIDTH` characters around the
+/// first exact term hit. Works on chars so multi-byte text is never split.
+fn make_snippet(line: &str, terms: &[String]) -> String {
+    let chars: Vec<char> = line.trim().chars().collect();
+    if chars.len() <= SNIPPET_WIDTH {
+        return chars.into_iter().collect();
+    }
+
+    let normalized: Vec<char> = chars
+        .iter()
+        .map(|c| normalize_for_search(&c.to_string()).chars().next().unwrap_or(*c))
+        .collect();
+    let haystack: String = normalized.iter().collect();
+
+    let match_pos = terms
+        .iter()
+        .filter_map(|t| haystack.find(t.as_str()))
+        .min()
+        .map(|byte_pos| haystack[..byte_pos].chars().count())
+        .unwrap_or(0);
+
+    let start = match_pos.saturating_sub(SNIPPET_WIDTH / 3);
+    let end = (start + SNIPPET_WIDTH).min(chars.len());
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    snippet.extend(&chars[start..end]);
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(query: &str) -> Vec<String> {
+        query.split_whitespace().map(normalize_for_search).collect()
+    }
+
+    #[test]
+    fn normalizes_full_width_text() {
+        assert_eq!(normalize_for_search("ＳＮＡＩＬ\u{3000}メモ"), "snail メモ");
+    }
+
+    #[test]
+    fn match_strength_uses_bigrams() {
+        let line = "議事録の作成";
+        let bigrams = char_bigrams(line);
+        assert_eq!(match_strength(line, &bigrams, "議事録"), 1.0);
+        assert_eq!(match_strength(line, &bigrams, "議事録作成"), 0.75);
+        assert_eq!(match_strength(line, &bigrams, "予算会議"), 0.0);
+    }
+
+    #[test]
+    fn cjk_query_without_spaces_matches_partially() {
+        let content = "---\ntitle: 定例\n---\n# 定例\n\n会議の議事録をまとめる\n";
+        let hit = score_note(content, &terms("会議議事録")).unwrap();
+        assert_eq!(hit.line, 6);
+        assert_eq!(hit.snippet, "会議の議事録をまとめる");
+        assert!(score_note(content, &terms("予算会議")).is_none());
+    }
+
+    #[test]
+    fn title_outranks_body() {
+        let in_title = score_note("# Snail roadmap\n\nplans\n", &terms("snail")).unwrap();
+        let in_frontmatter = score_note("---\ntags: snail\n---\n# Roadmap\n", &terms("snail")).unwrap();
+        let in_body = score_note("# Roadmap\n\nsnail plans\n", &terms("snail")).unwrap();
+        assert!(in_title.score > in_frontmatter.score);
+        assert!(in_frontmatter.score > in_body.score);
+        assert_eq!(in_title.title, "Snail roadmap");
+    }
+
+    #[test]
+    fn every_term_must_match() {
+        let content = "# Meeting\n\nbudget review\n";
+        assert!(score_note(content, &terms("budget meeting")).is_some());
+        assert!(score_note(content, &terms("budget travel")).is_none());
+    }
+
+    #[test]
+    fn snippet_centers_on_the_hit() {
+        let line = format!("{}議事録{}", "あ".repeat(50), "い".repeat(50));
+        let snippet = make_snippet(&line, &terms("議事録"));
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert!(snippet.contains("議事録"));
+        assert_eq!(snippet.chars().count(), SNIPPET_WIDTH + 2);
+
+        assert_eq!(make_snippet("  short line  ", &terms("short")), "short line");
+    }
 }
@@ -2,3 +2,6 @@ pub mod memo;
 pub mod todo;
 pub mod project;
 pub mod gtd;
+pub mod next;
+pub mod agenda;
+pub mod log;
@@ -0,0 +1,218 @@
+use anyhow::Result;
+use chrono::Local;
+use clap::ValueEnum;
+use crate::cli::{Energy, OutputArgs, OutputFormat};
+use crate::commands::todo::{collect_active_todos, collect_dependency_todos, is_deferred, TodoItem};
+use crate::config::Config;
+use crate::deps::DependencyGraph;
+use crate::note::Note;
+use crate::output::{print_records, Record};
+use crate::utils::{open_editor, prompt_selection};
+
+const NO_CONTEXT: &str = "(no context)";
+
+/// List next actions (`status: next`) grouped by context. Deferred tasks and
+/// tasks blocked by open dependencies are left out.
+pub fn next(
+    contexts: &[String],
+    time: Option<u32>,
+    energy: Option<Energy>,
+    output: &OutputArgs,
+    config: &Config,
+) -> Result<()> {
+    let root_dir = config.root_dir()?;
+    let date_format = &config.general.date_format;
+    let today = Local::now().date_naive();
+
+    let mut todos = collect_active_todos(config)?;
+    todos.retain(|t| t.status == "next" && !is_deferred(&t.note, today, date_format));
+
+    let all_todos = collect_dependency_todos(&todos, config)?;
+    let graph = DependencyGraph::new(all_todos.iter().map(|t| &t.note));
+    todos.retain(|t| graph.blockers(&t.path).is_empty());
+
+    // Tasks without an estimate (or energy) can't be judged, so they drop out
+    if let Some(minutes) = time {
+        todos.retain(|t| t.note.frontmatter.estimate_minutes().map(|m| m <= minutes).unwrap_or(false));
+    }
+    if let Some(max) = energy {
+        todos.retain(|t| task_energy(&t.note).map(|e| e <= max).unwrap_or(false));
+    }
+
+    // Tasks with a due date first, soonest first
+    todos.sort_by_key(|t| {
+        let due = t.note.frontmatter.due_date(date_format);
+        (due.is_none(), due, t.title.clone())
+    });
+
+    // A task shows up under each of its contexts
+    let wanted: Vec<String> = contexts.iter().map(|c| normalize(c)).collect();
+    let mut groups: Vec<(String, Vec<&TodoItem>)> = Vec::new();
+    for todo in &todos {
+        let mut found = note_contexts(&todo.note);
+        if found.is_empty() {
+            found.push(NO_CONTEXT.to_string());
+        }
+        for context in found {
+            if !wanted.is_empty() && !wanted.contains(&context) {
+                continue;
+            }
+            match groups.iter_mut().find(|(c, _)| *c == context) {
+                Some((_, items)) => items.push(todo),
+                None => groups.push((context, vec![todo])),
+            }
+        }
+    }
+    groups.sort_by_key(|(c, _)| (c == NO_CONTEXT, c.clone()));
+
+    if output.format != OutputFormat::Text {
+        let records: Vec<Record> = groups
+            .iter()
+            .flat_map(|(context, items)| items.iter().map(move |t| (context, t)))
+            .map(|(context, t)| {
+                Record::new()
+                    .field("context", context.as_str())
+                    .field("title", t.title.as_str())
+                    .field("project", t.project.as_str())
+                    .field("due", t.due.as_str())
+                    .field("estimate", t.note.frontmatter.estimate().unwrap_or_default())
+                    .field("energy", task_energy(&t.note).map(energy_name).unwrap_or(""))
+                    .paths(&t.path, &root_dir)
+                    .frontmatter(&t.note.frontmatter)
+            })
+            .collect();
+        return print_records(output.format, &records);
+    }
+
+    if groups.is_empty() {
+        println!("No next actions found.");
+        return Ok(());
+    }
+
+    let mut openable = Vec::new();
+    for (context, items) in &groups {
+        println!("{} ({})", context, items.len());
+        for todo in items {
+            openable.push(&todo.path);
+            let project_str = if todo.project.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", todo.project)
+            };
+            let estimate_str = match todo.note.frontmatter.estimate() {
+                Some(estimate) => format!(" ({})", estimate),
+                None => String::new(),
+            };
+            let due_str = if todo.due.is_empty() {
+                String::new()
+            } else {
+                format!(" (due: {})", todo.due)
+            };
+            println!("  {:>2}. {}{}{}{}", openable.len(), todo.title, project_str, estimate_str, due_str);
+        }
+        println!();
+    }
+
+    let mut shown: Vec<_> = openable.clone();
+    shown.sort();
+    shown.dedup();
+    println!("Total: {} next action(s)", shown.len());
+
+    if output.interactive() {
+        if let Some(selection) = prompt_selection("Open file", openable.len())? {
+            open_editor(openable[selection], &config.general.editor)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// `phone`, `@Phone` -> `@phone`
+fn normalize(context: &str) -> String {
+    format!("@{}", context.trim().trim_start_matches('@').to_lowercase())
+}
+
+/// Contexts from `context:` plus `@words` in the body. Code blocks and HTML
+/// comments (like the template's list of example contexts) are ignored.
+fn note_contexts(note: &Note) -> Vec<String> {
+    let mut contexts: Vec<String> = Vec::new();
+    let mut add = |context: &str| {
+        let context = normalize(context);
+        if context.len() > 1 && !contexts.contains(&context) {
+            contexts.push(context);
+        }
+    };
+
+    for context in note.frontmatter.contexts() {
+        add(&context);
+    }
+
+    let mut in_fence = false;
+    let mut in_comment = false;
+    for line in note.body.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        // Keep only the text outside <!-- ... -->
+        let mut text = String::new();
+        let mut rest = line;
+        loop {
+            let marker = if in_comment { "-->" } else { "<!--" };
+            match rest.find(marker) {
+                Some(i) => {
+                    if !in_comment {
+                        text.push_str(&rest[..i]);
+                    }
+                    rest = &rest[i + marker.len()..];
+                    in_comment = !in_comment;
+                }
+                None => {
+                    if !in_comment {
+                        text.push_str(rest);
+                    }
+                    break;
+                }
+            }
+        }
+
+        for word in text.split_whitespace() {
+            if let Some(name) = word.strip_prefix('@') {
+                let name: String = name
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
+                    .collect();
+                add(&name);
+            }
+        }
+    }
+
+    contexts
+}
+
+/// `energy:` from the frontmatter, or judged from `estimate:`
+fn task_energy(note: &Note) -> Option<Energy> {
+    let fm = &note.frontmatter;
+    if let Some(energy) = fm.get_str("energy").and_then(|e| Energy::from_str(&e, true).ok()) {
+        return Some(energy);
+    }
+    let energy = match fm.estimate_minutes()? {
+        0..=15 => Energy::Low,
+        16..=60 => Energy::Medium,
+        _ => Energy::High,
+    };
+    Some(energy)
+}
+
+fn energy_name(energy: Energy) -> &'static str {
+    match energy {
+        Energy::Low => "low",
+        Energy::Medium => "medium",
+        Energy::High => "high",
+    }
+}
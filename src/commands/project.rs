@@ -1,11 +1,31 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
 use std::fs;
-use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use crate::commands::todo::{collect_all_todos_recursive, collect_every_todo, TodoItem};
+use crate::cli::{OutputArgs, OutputFormat};
 use crate::config::Config;
-use crate::utils::{create_file_from_template, get_current_date, open_editor};
+use crate::deps::{is_closed_status, DependencyGraph};
+use crate::note::Note;
+use crate::output::{print_records, Record};
+use crate::utils::{create_file_from_template, get_current_date, open_editor, prompt_selection};
 
 pub fn new(name: &str, no_edit: bool, config: &Config) -> Result<()> {
+    let (new_project_dir, readme_path) = create_project(name, config)?;
+
+    println!("Created project: {}", new_project_dir.display());
+    println!("README: {}", readme_path.display());
+
+    if !no_edit {
+        open_editor(&readme_path, &config.general.editor)?;
+    }
+
+    Ok(())
+}
+
+/// Create the next numbered project directory with its README.
+/// Returns the project directory and the README path.
+pub fn create_project(name: &str, config: &Config) -> Result<(PathBuf, PathBuf)> {
     let project_dir = config.project_dir()?;
 
     // Find the maximum project number
@@ -29,14 +49,7 @@ pub fn new(name: &str, no_edit: bool, config: &Config) -> Result<()> {
 
     create_file_from_template(&template_path, &readme_path, &replacements)?;
 
-    println!("Created project: {}", new_project_dir.display());
-    println!("README: {}", readme_path.display());
-
-    if !no_edit {
-        open_editor(&readme_path, &config.general.editor)?;
-    }
-
-    Ok(())
+    Ok((new_project_dir, readme_path))
 }
 
 fn find_max_project_number(project_dir: &std::path::Path) -> Result<u32> {
@@ -64,17 +77,66 @@ fn find_max_project_number(project_dir: &std::path::Path) -> Result<u32> {
     Ok(max_number)
 }
 
-pub fn list(config: &Config) -> Result<()> {
+pub fn list(output: &OutputArgs, config: &Config) -> Result<()> {
     let project_dir = config.project_dir()?;
+    let root_dir = config.root_dir()?;
+
+    let projects = if project_dir.exists() {
+        collect_projects(&project_dir)?
+    } else {
+        Vec::new()
+    };
+
+    if output.format != OutputFormat::Text {
+        let records: Vec<Record> = projects
+            .iter()
+            .map(|p| {
+                let readme = p.readme.as_ref().and_then(|r| Note::load(r).ok());
+                let mut record = Record::new()
+                    .field("number", p.number)
+                    .field("name", p.name.as_str())
+                    .paths(&p.path, &root_dir)
+                    .field("readme", p.readme.as_ref().map(|r| r.display().to_string()));
+                if let Some(readme) = readme {
+                    record = record.frontmatter(&readme.frontmatter);
+                }
+                record
+            })
+            .collect();
+        return print_records(output.format, &records);
+    }
 
-    if !project_dir.exists() {
+    if projects.is_empty() {
         println!("No projects found.");
         return Ok(());
     }
 
+    // Display projects
+    for (i, project) in projects.iter().enumerate() {
+        println!("{}: {:05}_{}", i + 1, project.number, project.name);
+    }
+
+    println!("\nTotal: {} project(s)", projects.len());
+
+    if output.interactive() {
+        if let Some(selection) = prompt_selection("Open README", projects.len())? {
+            let project = &projects[selection];
+            if let Some(ref readme) = project.readme {
+                open_editor(readme, &config.general.editor)?;
+            } else {
+                println!("No README found in project: {}", project.name);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Collect project directories sorted by project number
+fn collect_projects(project_dir: &Path) -> Result<Vec<ProjectItem>> {
     let mut projects: Vec<ProjectItem> = Vec::new();
 
-    for entry in fs::read_dir(&project_dir)? {
+    for entry in fs::read_dir(project_dir)? {
         let entry = entry?;
         let path = entry.path();
 
@@ -84,14 +146,7 @@ pub fn list(config: &Config) -> Result<()> {
                 .unwrap_or("")
                 .to_string();
 
-            // Parse project number and name (e.g., "00831_myproject")
-            let (number, name) = if let Some(idx) = dir_name.find('_') {
-                let num = dir_name[..idx].parse::<u32>().unwrap_or(0);
-                let name = dir_name[idx + 1..].to_string();
-                (num, name)
-            } else {
-                (0, dir_name.clone())
-            };
+            let (number, name) = parse_project_dir_name(&dir_name);
 
             // Find README file
             let readme = find_readme(&path);
@@ -105,45 +160,21 @@ pub fn list(config: &Config) -> Result<()> {
         }
     }
 
-    if projects.is_empty() {
-        println!("No projects found.");
-        return Ok(());
-    }
-
     // Sort by project number
-    projects.sort_by(|a, b| a.number.cmp(&b.number));
-
-    // Display projects
-    for (i, project) in projects.iter().enumerate() {
-        println!("{}: {:05}_{}", i + 1, project.number, project.name);
-    }
+    projects.sort_by_key(|p| p.number);
 
-    println!("\nTotal: {} project(s)", projects.len());
+    Ok(projects)
+}
 
-    // Prompt for selection
-    print!("Open README (1-{}, or Enter to skip): ", projects.len());
-    io::stdout().flush()?;
-
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-    let input = input.trim();
-
-    if !input.is_empty() {
-        if let Ok(selection) = input.parse::<usize>() {
-            if selection >= 1 && selection <= projects.len() {
-                let project = &projects[selection - 1];
-                if let Some(ref readme) = project.readme {
-                    open_editor(readme, &config.general.editor)?;
-                } else {
-                    println!("No README found in project: {}", project.name);
-                }
-            } else {
-                println!("Invalid selection: {}", selection);
-            }
-        }
+/// Parse project number and name (e.g., "00831_myproject")
+fn parse_project_dir_name(dir_name: &str) -> (u32, String) {
+    if let Some(idx) = dir_name.find('_') {
+        let num = dir_name[..idx].parse::<u32>().unwrap_or(0);
+        let name = dir_name[idx + 1..].to_string();
+        (num, name)
+    } else {
+        (0, dir_name.to_string())
     }
-
-    Ok(())
 }
 
 struct ProjectItem {
@@ -153,7 +184,7 @@ struct ProjectItem {
     readme: Option<PathBuf>,
 }
 
-fn find_readme(project_path: &PathBuf) -> Option<PathBuf> {
+fn find_readme(project_path: &Path) -> Option<PathBuf> {
     if let Ok(entries) = fs::read_dir(project_path) {
         for entry in entries.flatten() {
             let path = entry.path();
@@ -169,7 +200,240 @@ fn find_readme(project_path: &PathBuf) -> Option<PathBuf> {
     None
 }
 
-pub fn show(_name: &str, _config: &Config) -> Result<()> {
-    println!("Project show command - not yet implemented");
+pub fn show(name: &str, config: &Config) -> Result<()> {
+    let project_dir = config.project_dir()?;
+
+    if !project_dir.exists() {
+        anyhow::bail!("Project not found: {}", name);
+    }
+
+    let projects = collect_projects(&project_dir)?;
+    let project = find_project(&projects, name)?;
+    let dir_name = format!("{:05}_{}", project.number, project.name);
+    let root_dir = config.root_dir()?;
+    let today = Local::now().date_naive();
+
+    println!("{}", dir_name);
+    let display_path = project.path.strip_prefix(&root_dir)
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| project.path.display().to_string());
+    println!("   {}", display_path);
+
+    // README overview
+    println!("\n## Overview");
+    let overview = project.readme.as_ref()
+        .and_then(|readme| fs::read_to_string(readme).ok())
+        .map(|content| extract_overview(&content))
+        .unwrap_or_default();
+    if overview.is_empty() {
+        println!("(no overview)");
+    } else {
+        for line in &overview {
+            println!("{}", line);
+        }
+    }
+
+    // Todos inside the project directory, plus todos elsewhere that name the project
+    let mut todos: Vec<TodoItem> = Vec::new();
+    collect_all_todos_recursive(&project.path, &mut todos)?;
+
+    let mut linked: Vec<TodoItem> = Vec::new();
+    for dir in [
+        config.inbox_dir()?,
+        config.next_dir()?,
+        config.waiting_dir()?,
+        config.someday_dir()?,
+        config.archive_dir()?,
+    ] {
+        if dir.exists() {
+            collect_all_todos_recursive(&dir, &mut linked)?;
+        }
+    }
+    linked.retain(|t| t.project == project.name || t.project == dir_name);
+    todos.extend(linked);
+
+    // Group by status, keeping the GTD flow order and closed statuses last
+    let status_order = ["inbox", "next", "waiting", "someday"];
+    let mut statuses: Vec<String> = todos.iter().map(|t| t.status.clone()).collect();
+    statuses.sort_by_key(|s| {
+        let rank = status_order.iter().position(|o| o == s).unwrap_or(status_order.len());
+        let closed = is_closed_status(s);
+        (closed, rank, s.clone())
+    });
+    statuses.dedup();
+
+    let mut checked_total = 0;
+    let mut checkbox_total = 0;
+    let mut overdue: Vec<&TodoItem> = Vec::new();
+    let mut last_activity = project.readme.as_deref().and_then(modified_date);
+
+    println!("\n## Tasks");
+    if todos.is_empty() {
+        println!("(no tasks)");
+    }
+
+    for status in &statuses {
+        let group: Vec<&TodoItem> = todos.iter().filter(|t| &t.status == status).collect();
+        println!("\n[{}] {} task(s)", status, group.len());
+
+        for todo in group {
+            let (checked, total) = todo.note.checklist();
+            checked_total += checked;
+            checkbox_total += total;
+
+            let is_closed = is_closed_status(&todo.status);
+            let is_overdue = !is_closed
+                && todo.note.frontmatter.due_date(&config.general.date_format)
+                    .map(|d| d < today)
+                    .unwrap_or(false);
+            if is_overdue {
+                overdue.push(todo);
+            }
+
+            if let Some(modified) = modified_date(&todo.path) {
+                if last_activity.as_ref().map(|l| modified > *l).unwrap_or(true) {
+                    last_activity = Some(modified);
+                }
+            }
+
+            let checklist_str = if total > 0 {
+                format!(" ({}/{})", checked, total)
+            } else {
+                String::new()
+            };
+            let due_str = if todo.due.is_empty() {
+                String::new()
+            } else if is_overdue {
+                format!(" (due: {}, OVERDUE)", todo.due)
+            } else {
+                format!(" (due: {})", todo.due)
+            };
+            println!("  - {}{}{}", todo.title, checklist_str, due_str);
+        }
+    }
+
+    // Open tasks in dependency order, when any of them declare `depends_on:`
+    let open_todos: Vec<&TodoItem> = todos
+        .iter()
+        .filter(|t| !is_closed_status(&t.status))
+        .collect();
+    if open_todos.iter().any(|t| t.note.frontmatter.contains_key("depends_on")) {
+        let all_todos = collect_every_todo(config)?;
+        let graph = DependencyGraph::new(all_todos.iter().map(|t| &t.note));
+        graph.report_problems();
+
+        println!("\n## Dependency order");
+        let paths: Vec<&Path> = open_todos.iter().map(|t| t.path.as_path()).collect();
+        for (i, note) in graph.order(&paths).iter().enumerate() {
+            let blockers: Vec<String> = graph.blockers(&note.path).iter().map(|n| n.stem()).collect();
+            let blocked_str = if blockers.is_empty() {
+                String::new()
+            } else {
+                format!(" [blocked by: {}]", blockers.join(", "))
+            };
+            println!("{}. {}{}", i + 1, note.title(), blocked_str);
+        }
+    }
+
+    // Summary
+    let open_count = todos.iter().filter(|t| !is_closed_status(&t.status)).count();
+    let done_count = todos.iter().filter(|t| t.status == "done").count();
+
+    println!("\n## Summary");
+    println!("Tasks: {} open, {} done", open_count, done_count);
+    println!("Checklist: {}/{}", checked_total, checkbox_total);
+    println!("Overdue: {}", overdue.len());
+    for todo in &overdue {
+        println!("  - {} (due: {})", todo.title, todo.due);
+    }
+    let last_activity_str = last_activity
+        .map(|d| d.format(&config.general.date_format).to_string())
+        .unwrap_or_else(|| "-".to_string());
+    println!("Last activity: {}", last_activity_str);
+
     Ok(())
 }
+
+/// Resolve a project by number, name or directory name.
+/// Returns the project name and its directory.
+pub fn lookup_project(query: &str, config: &Config) -> Result<(String, PathBuf)> {
+    let project_dir = config.project_dir()?;
+    if !project_dir.exists() {
+        anyhow::bail!("Project not found: {}", query);
+    }
+
+    let projects = collect_projects(&project_dir)?;
+    let project = find_project(&projects, query)?;
+    Ok((project.name.clone(), project.path.clone()))
+}
+
+/// Find a project by number ("831", "00831"), name, or full directory name.
+/// Falls back to a unique partial name match.
+fn find_project<'a>(projects: &'a [ProjectItem], query: &str) -> Result<&'a ProjectItem> {
+    if let Ok(number) = query.parse::<u32>() {
+        if let Some(project) = projects.iter().find(|p| p.number == number) {
+            return Ok(project);
+        }
+    }
+
+    let (query_number, query_name) = parse_project_dir_name(query);
+    if let Some(project) = projects.iter().find(|p| {
+        p.name == query || (p.number == query_number && p.name == query_name)
+    }) {
+        return Ok(project);
+    }
+
+    let query_lower = query.to_lowercase();
+    let candidates: Vec<&ProjectItem> = projects
+        .iter()
+        .filter(|p| p.name.to_lowercase().contains(&query_lower))
+        .collect();
+
+    match candidates.len() {
+        0 => anyhow::bail!("Project not found: {}", query),
+        1 => Ok(candidates[0]),
+        _ => {
+            let names: Vec<String> = candidates
+                .iter()
+                .map(|p| format!("{:05}_{}", p.number, p.name))
+                .collect();
+            anyhow::bail!("Multiple projects match '{}': {}", query, names.join(", "))
+        }
+    }
+}
+
+/// Lines of the README's overview section, without the heading and surrounding blanks
+fn extract_overview(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut in_overview = false;
+
+    for line in content.lines() {
+        if line.starts_with("## ") {
+            if in_overview {
+                break;
+            }
+            in_overview = line.to_lowercase().contains("overview") || line.contains("概要");
+            continue;
+        }
+
+        if in_overview {
+            lines.push(line.to_string());
+        }
+    }
+
+    while lines.first().map(|l| l.trim().is_empty()).unwrap_or(false) {
+        lines.remove(0);
+    }
+    while lines.last().map(|l| l.trim().is_empty()).unwrap_or(false) {
+        lines.pop();
+    }
+
+    lines
+}
+
+fn modified_date(path: &Path) -> Option<DateTime<Local>> {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(DateTime::<Local>::from)
+}
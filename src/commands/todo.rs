@@ -1,24 +1,91 @@
 use anyhow::{Context, Result};
+use chrono::{Local, NaiveDate};
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use crate::cli::{OutputArgs, OutputFormat};
+use crate::commands::gtd::record_closed_todo;
+use crate::commands::project::{create_project, lookup_project};
 use crate::config::Config;
-use crate::utils::{create_file_from_base_and_snip, create_file_from_template, get_current_date, open_editor, sanitize_filename};
+use crate::dates::parse_date;
+use crate::deps::{is_closed_status, is_open, DependencyGraph};
+use crate::note::{collect_notes, Note};
+use crate::output::{print_records, Record};
+use crate::query::{Query, QueryContext};
+use crate::repeat::Repeat;
+use crate::utils::{create_file_from_base_and_snip, create_file_from_template, get_current_date, open_editor, prompt_selection, sanitize_filename};
+
+pub fn new(title: &str, project: Option<&str>, due: Option<&str>, no_edit: bool, config: &Config) -> Result<()> {
+    // Resolve the due date first so a bad expression creates nothing
+    let due = due.map(|expr| resolve_due(expr, config)).transpose()?;
+
+    let file_path = create_todo(title, project.unwrap_or(""), "inbox", &config.inbox_dir()?, config)?;
+
+    if let Some(due) = due {
+        let mut note = Note::load(&file_path)?;
+        note.frontmatter.set_due(&due);
+        note.save()?;
+    }
+
+    println!("Created todo: {}", file_path.display());
+
+    if !no_edit {
+        open_editor(&file_path, &config.general.editor)?;
+    }
+
+    Ok(())
+}
+
+/// Set or clear (`none`) a todo's due date from a date expression
+pub fn due(file: &str, expr: &str, config: &Config) -> Result<()> {
+    let file_path = resolve_todo_file(file, config)?;
+
+    let mut note = Note::load(&file_path)?;
+    if !note.frontmatter.is_present() {
+        anyhow::bail!("No frontmatter found");
+    }
+
+    if expr.trim().eq_ignore_ascii_case("none") {
+        note.frontmatter.set_due("");
+        note.save()?;
+        println!("Cleared due date: {}", file_path.display());
+        return Ok(());
+    }
 
-pub fn new(title: &str, project: Option<&str>, no_edit: bool, config: &Config) -> Result<()> {
+    let due = resolve_due(expr, config)?;
+    note.frontmatter.set_due(&due);
+    note.save()?;
+
+    println!("Due {}: {}", due, file_path.display());
+
+    Ok(())
+}
+
+/// Parse a date expression relative to today into `general.date_format`
+fn resolve_due(expr: &str, config: &Config) -> Result<String> {
+    let date_format = &config.general.date_format;
+    let today = Local::now().date_naive();
+    let date = parse_date(expr, today, date_format)
+        .ok_or_else(|| anyhow::anyhow!("Invalid date: '{}' (try tomorrow, fri, +3d, next month or YYYY-MM-DD)", expr))?;
+    Ok(date.format(date_format).to_string())
+}
+
+/// Path a new todo with this title gets in `dir`
+fn todo_path(title: &str, dir: &Path, config: &Config) -> PathBuf {
     let date = get_current_date(&config.general.date_format);
-    let sanitized_title = sanitize_filename(title);
-    let filename = format!("{}-{}.md", date, sanitized_title);
+    dir.join(format!("{}-{}.md", date, sanitize_filename(title)))
+}
 
-    let inbox_dir = config.inbox_dir()?;
-    let file_path = inbox_dir.join(&filename);
+/// Create a todo from the base + todo templates in `dir`. Returns its path.
+pub fn create_todo(title: &str, project: &str, status: &str, dir: &Path, config: &Config) -> Result<PathBuf> {
+    let date = get_current_date(&config.general.date_format);
+    let file_path = todo_path(title, dir, config);
 
-    let project_str = project.unwrap_or("");
     let replacements = vec![
         ("title", title),
         ("date", &date),
-        ("status", "inbox"),
-        ("project", project_str),
+        ("status", status),
+        ("project", project),
     ];
 
     let base_path = config.get_template_path("base");
@@ -34,70 +101,74 @@ pub fn new(title: &str, project: Option<&str>, no_edit: bool, config: &Config) -
         create_file_from_template(&snip_path, &file_path, &replacements)?;
     }
 
-    println!("Created todo: {}", file_path.display());
-
-    if !no_edit {
-        open_editor(&file_path, &config.general.editor)?;
-    }
-
-    Ok(())
+    Ok(file_path)
 }
 
-pub fn list(filters: &[String], config: &Config) -> Result<()> {
-    let mut todos: Vec<TodoItem> = Vec::new();
+pub fn list(filters: &[String], archive: bool, hide_blocked: bool, deferred: bool, output: &OutputArgs, config: &Config) -> Result<()> {
     let root_dir = config.root_dir()?;
-    let today = get_current_date(&config.general.date_format);
-
-    // Parse filters
-    let mut status_filter: Option<String> = None;
-    let mut due_filter: Option<String> = None;
-
-    for filter in filters {
-        if let Some((key, value)) = filter.split_once(':') {
-            match key {
-                "status" => status_filter = Some(value.to_string()),
-                "due" => due_filter = Some(value.to_string()),
-                _ => {}
-            }
-        }
-    }
+    let query = Query::parse_all(filters)?;
+    let ctx = QueryContext::from_config(config);
 
-    // Search in INBOX, NEXTACTION, and project directories
-    let search_dirs = vec![
-        config.inbox_dir()?,
-        config.next_dir()?,
-    ];
+    let mut todos = collect_active_todos(config)?;
 
-    for dir in search_dirs {
-        if dir.exists() {
-            collect_todos(&dir, &mut todos)?;
-        }
+    // Archived todos (done / canceled) on request
+    let archive_dir = config.archive_dir()?;
+    if archive && archive_dir.exists() {
+        collect_all_todos_recursive(&archive_dir, &mut todos)?;
     }
 
-    // Search in project directories (recursive)
-    let project_dir = config.project_dir()?;
-    if project_dir.exists() {
-        collect_todos_recursive(&project_dir, &mut todos)?;
+    // Apply filters
+    if let Some(ref query) = query {
+        todos.retain(|t| query.matches(&t.note, &ctx));
     }
 
-    // Apply filters
-    if let Some(ref status) = status_filter {
-        todos.retain(|t| t.status == *status);
+    // Deferred todos stay out of sight until their review_date
+    let date_format = &config.general.date_format;
+    if !deferred {
+        todos.retain(|t| !is_deferred(&t.note, ctx.today, date_format));
     }
 
-    if let Some(ref due) = due_filter {
-        match due.as_str() {
-            "today" => {
-                todos.retain(|t| t.due == today);
-            }
-            "overdue" => {
-                todos.retain(|t| !t.due.is_empty() && t.due < today);
-            }
-            _ => {
-                // Treat as exact date match
-                todos.retain(|t| t.due == *due);
-            }
-        }
+    // Sort by created date (newest first)
+    todos.sort_by_key(|t| std::cmp::Reverse(t.note.frontmatter.created_date(date_format)));
+
+    // Open dependencies of each todo, resolved against every known todo
+    let all_todos = collect_dependency_todos(&todos, config)?;
+    let graph = DependencyGraph::new(all_todos.iter().map(|t| &t.note));
+    graph.report_problems();
+    let blocked_by: Vec<Vec<String>> = todos
+        .iter()
+        .map(|t| graph.blockers(&t.path).iter().map(|n| n.stem()).collect())
+        .collect();
+    let (todos, blocked_by): (Vec<TodoItem>, Vec<Vec<String>>) = todos
+        .into_iter()
+        .zip(blocked_by)
+        .filter(|(_, blockers)| !hide_blocked || blockers.is_empty())
+        .unzip();
+
+    if output.format != OutputFormat::Text {
+        let records: Vec<Record> = todos
+            .iter()
+            .zip(&blocked_by)
+            .map(|(t, blockers)| {
+                let (checked, total) = t.note.checklist();
+                Record::new()
+                    .field("title", t.title.as_str())
+                    .field("status", t.status.as_str())
+                    .field("project", t.project.as_str())
+                    .field("due", t.due.as_str())
+                    .field("created", t.created.as_str())
+                    .field("checklist", format!("{}/{}", checked, total))
+                    .field("checklist_checked", checked)
+                    .field("checklist_total", total)
+                    .field("blocked_by", blockers.clone())
+                    .field("waiting_on", t.note.frontmatter.get_str("waiting_on").unwrap_or_default())
+                    .field("days_outstanding", days_outstanding(t, ctx.today, date_format))
+                    .field("follow_up_overdue", follow_up_overdue(t, ctx.today, date_format))
+                    .paths(&t.path, &root_dir)
+                    .frontmatter(&t.note.frontmatter)
+            })
+            .collect();
+        return print_records(output.format, &records);
     }
 
     if todos.is_empty() {
@@ -105,18 +176,25 @@ pub fn list(filters: &[String], config: &Config) -> Result<()> {
         return Ok(());
     }
 
-    // Sort by created date (newest first)
-    todos.sort_by(|a, b| b.created.cmp(&a.created));
-
     // Display todos
-    for (i, todo) in todos.iter().enumerate() {
+    for (i, (todo, blockers)) in todos.iter().zip(&blocked_by).enumerate() {
+        let (checked, total) = todo.note.checklist();
+        let checklist_str = if total > 0 {
+            format!(" ({}/{})", checked, total)
+        } else {
+            String::new()
+        };
         let project_str = if todo.project.is_empty() {
             String::new()
         } else {
             format!(" [{}]", todo.project)
         };
+        let is_overdue = todo.is_open()
+            && todo.note.frontmatter.due_date(date_format).map(|d| d < ctx.today).unwrap_or(false);
         let due_str = if todo.due.is_empty() {
             String::new()
+        } else if is_overdue {
+            format!(" (due: {}, OVERDUE)", todo.due)
         } else {
             format!(" (due: {})", todo.due)
         };
@@ -124,27 +202,40 @@ pub fn list(filters: &[String], config: &Config) -> Result<()> {
         let display_path = todo.path.strip_prefix(&root_dir)
             .map(|p| p.display().to_string())
             .unwrap_or_else(|_| todo.path.display().to_string());
-        println!("{}: {} - {}{}{}", i + 1, todo.created, todo.title, project_str, due_str);
+        let blocked_str = if blockers.is_empty() {
+            String::new()
+        } else {
+            format!(" [blocked by: {}]", blockers.join(", "))
+        };
+        let waiting_str = match days_outstanding(todo, ctx.today, date_format) {
+            Some(days) => {
+                let who = todo.note.frontmatter.get_str("waiting_on").unwrap_or_else(|| "someone".to_string());
+                let follow_up = todo.note.frontmatter.get_str("follow_up").unwrap_or_default();
+                let follow_up_str = if follow_up_overdue(todo, ctx.today, date_format) {
+                    format!(", follow up: {}, OVERDUE", follow_up)
+                } else if !follow_up.is_empty() {
+                    format!(", follow up: {}", follow_up)
+                } else {
+                    String::new()
+                };
+                format!(" [waiting on {} for {} day(s){}]", who, days, follow_up_str)
+            }
+            None => String::new(),
+        };
+        let deferred_str = if is_deferred(&todo.note, ctx.today, date_format) {
+            format!(" (deferred until {})", todo.note.frontmatter.get_str("review_date").unwrap_or_default())
+        } else {
+            String::new()
+        };
+        println!("{}: {} - {}{}{}{}{}{}{}", i + 1, todo.created, todo.title, checklist_str, project_str, due_str, waiting_str, blocked_str, deferred_str);
         println!("   {}", display_path);
     }
 
     println!("\nTotal: {} todo(s)", todos.len());
 
-    // Prompt for selection
-    print!("Open file (1-{}, or Enter to skip): ", todos.len());
-    io::stdout().flush()?;
-
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-    let input = input.trim();
-
-    if !input.is_empty() {
-        if let Ok(selection) = input.parse::<usize>() {
-            if selection >= 1 && selection <= todos.len() {
-                open_editor(&todos[selection - 1].path, &config.general.editor)?;
-            } else {
-                println!("Invalid selection: {}", selection);
-            }
+    if output.interactive() {
+        if let Some(selection) = prompt_selection("Open file", todos.len())? {
+            open_editor(&todos[selection].path, &config.general.editor)?;
         }
     }
 
@@ -152,123 +243,442 @@ pub fn list(filters: &[String], config: &Config) -> Result<()> {
 }
 
 #[derive(Debug)]
-struct TodoItem {
-    title: String,
-    status: String,
-    project: String,
-    due: String,
-    created: String,
-    path: PathBuf,
+pub struct TodoItem {
+    pub title: String,
+    pub status: String,
+    pub project: String,
+    pub due: String,
+    pub created: String,
+    pub completed: String,
+    pub path: PathBuf,
+    pub note: Note,
 }
 
-fn parse_frontmatter(content: &str) -> Option<(String, String, String, String)> {
-    let lines: Vec<&str> = content.lines().collect();
+impl TodoItem {
+    /// A note is a todo when its frontmatter has a non-empty `status:`
+    fn from_note(note: Note) -> Option<Self> {
+        let fm = &note.frontmatter;
+        let status = fm.status()?;
+        Some(Self {
+            title: note.title(),
+            status,
+            project: fm.project().unwrap_or_default(),
+            due: fm.due().unwrap_or_default(),
+            created: fm.created().unwrap_or_default(),
+            completed: fm.completed().unwrap_or_default(),
+            path: note.path.clone(),
+            note,
+        })
+    }
 
-    if lines.is_empty() || lines[0] != "---" {
+    fn is_open(&self) -> bool {
+        !is_closed_status(&self.status)
+    }
+}
+
+/// Days since a waiting todo was delegated (or created, if it never was)
+fn days_outstanding(todo: &TodoItem, today: NaiveDate, date_format: &str) -> Option<i64> {
+    if todo.status != "waiting" {
         return None;
     }
+    let fm = &todo.note.frontmatter;
+    let since = fm.get_date("delegated", date_format).or_else(|| fm.created_date(date_format))?;
+    Some((today - since).num_days())
+}
+
+/// Whether a waiting todo's `follow_up:` date has passed
+fn follow_up_overdue(todo: &TodoItem, today: NaiveDate, date_format: &str) -> bool {
+    todo.status == "waiting"
+        && todo.note.frontmatter
+            .get_date("follow_up", date_format)
+            .map(|d| d < today)
+            .unwrap_or(false)
+}
+
+/// Deferred (tickler) until a `review_date:` after `today`
+pub fn is_deferred(note: &Note, today: NaiveDate, date_format: &str) -> bool {
+    note.frontmatter
+        .review_date(date_format)
+        .map(|d| d > today)
+        .unwrap_or(false)
+}
+
+pub fn collect_todos(dir: &Path, todos: &mut Vec<TodoItem>) -> Result<()> {
+    let mut notes = Vec::new();
+    collect_notes(dir, false, &mut notes)?;
+    todos.extend(notes.into_iter().filter_map(TodoItem::from_note).filter(|t| t.is_open()));
+    Ok(())
+}
+
+fn collect_todos_recursive(dir: &Path, todos: &mut Vec<TodoItem>) -> Result<()> {
+    let mut notes = Vec::new();
+    collect_notes(dir, true, &mut notes)?;
+    todos.extend(notes.into_iter().filter_map(TodoItem::from_note).filter(|t| t.is_open()));
+    Ok(())
+}
+
+/// Open todos in INBOX, NEXTACTION, WAITING, and project directories
+pub fn collect_active_todos(config: &Config) -> Result<Vec<TodoItem>> {
+    let mut todos: Vec<TodoItem> = Vec::new();
 
-    let mut end_index = None;
-    for (i, line) in lines.iter().enumerate().skip(1) {
-        if *line == "---" {
-            end_index = Some(i);
-            break;
+    let search_dirs = vec![
+        config.inbox_dir()?,
+        config.next_dir()?,
+        config.waiting_dir()?,
+    ];
+
+    for dir in search_dirs {
+        if dir.exists() {
+            collect_todos(&dir, &mut todos)?;
         }
     }
 
-    let end_index = end_index?;
+    // Search in project directories (recursive)
+    let project_dir = config.project_dir()?;
+    if project_dir.exists() {
+        collect_todos_recursive(&project_dir, &mut todos)?;
+    }
 
-    let mut status = String::new();
-    let mut project = String::new();
-    let mut due = String::new();
-    let mut created = String::new();
+    Ok(todos)
+}
 
-    for line in &lines[1..end_index] {
-        if let Some((key, value)) = line.split_once(':') {
-            let key = key.trim();
-            let value = value.trim();
-            match key {
-                "status" => status = value.to_string(),
-                "project" => project = value.trim_matches('"').to_string(),
-                "due" | "due_date" => due = value.to_string(),
-                "created" | "date" => created = value.to_string(),
-                _ => {}
-            }
+/// Every todo in the GTD directories, projects and the archive, open or closed
+pub fn collect_every_todo(config: &Config) -> Result<Vec<TodoItem>> {
+    let mut todos = Vec::new();
+    for dir in [
+        config.inbox_dir()?,
+        config.next_dir()?,
+        config.waiting_dir()?,
+        config.someday_dir()?,
+        config.project_dir()?,
+        config.archive_dir()?,
+    ] {
+        if dir.exists() {
+            collect_all_todos_recursive(&dir, &mut todos)?;
         }
     }
+    Ok(todos)
+}
+
+/// The todos to resolve dependencies of `todos` against: every todo when one
+/// of them declares `depends_on:`, otherwise none (nothing can be blocked, so
+/// the archive is not scanned)
+pub fn collect_dependency_todos(todos: &[TodoItem], config: &Config) -> Result<Vec<TodoItem>> {
+    if todos.iter().any(|t| t.note.frontmatter.contains_key("depends_on")) {
+        collect_every_todo(config)
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// Collect every todo under `dir` recursively, including done and canceled ones.
+pub fn collect_all_todos_recursive(dir: &Path, todos: &mut Vec<TodoItem>) -> Result<()> {
+    let mut notes = Vec::new();
+    collect_notes(dir, true, &mut notes)?;
+    todos.extend(notes.into_iter().filter_map(TodoItem::from_note));
+    Ok(())
+}
+
+pub fn done(file: &str, config: &Config) -> Result<()> {
+    let file_path = resolve_todo_file(file, config)?;
+    complete(&file_path, config)
+}
+
+/// Mark the todo at `file_path` done: archive it, create the next instance of
+/// a recurring todo and report the todos it unblocked
+pub fn complete(file_path: &Path, config: &Config) -> Result<()> {
+    let file_path = file_path.to_path_buf();
+
+    // Update frontmatter and move to the archive
+    let note = Note::load(&file_path)?;
+    if !note.frontmatter.is_present() {
+        anyhow::bail!("No frontmatter found");
+    }
+
+    // Todos waiting on this one, and whether it was their last open dependency
+    let all_todos = collect_every_todo(config)?;
+    let graph = DependencyGraph::new(all_todos.iter().map(|t| &t.note));
+    let done_path = graph.get(&file_path).map(|n| &n.path);
+    let unblocked: Vec<&Note> = graph
+        .dependents(&file_path)
+        .into_iter()
+        .filter(|n| is_open(n) && graph.blockers(&n.path).iter().all(|b| Some(&b.path) == done_path))
+        .collect();
+
+    let (dest_path, next) = archive_done(note, config)?;
+
+    println!("Marked as done: {}", file_path.display());
+    println!("Archived to: {}", dest_path.display());
 
-    Some((status, project, due, created))
+    for todo in &unblocked {
+        println!("Unblocked: {} ({})", todo.title(), todo.path.display());
+    }
+
+    if let Some(next) = next {
+        let due = next.frontmatter.due().unwrap_or_default();
+        println!("Next occurrence: {} (due: {})", next.path.display(), due);
+    }
+
+    Ok(())
 }
 
-fn extract_title(content: &str) -> String {
-    for line in content.lines() {
-        if line.starts_with("# ") {
-            return line[2..].trim().to_string();
+/// Hand a todo to someone else: status waiting, `waiting_on:`, `delegated:`,
+/// an optional `follow_up:` date and a line in the note's log
+pub fn delegate(file: &str, to: &str, follow_up: Option<&str>, config: &Config) -> Result<()> {
+    let file_path = resolve_todo_file(file, config)?;
+
+    let mut note = Note::load(&file_path)?;
+    if !note.frontmatter.is_present() {
+        anyhow::bail!("No frontmatter found");
+    }
+
+    let follow_up = follow_up.map(|expr| resolve_due(expr, config)).transpose()?;
+    let date = get_current_date(&config.general.date_format);
+
+    note.frontmatter.set("waiting_on", to);
+    note.frontmatter.set("delegated", &date);
+    let log = match follow_up {
+        Some(ref follow_up) => {
+            note.frontmatter.set("follow_up", follow_up);
+            format!("- {}: Delegated to {} (follow up: {})", date, to, follow_up)
         }
+        None => {
+            note.frontmatter.remove("follow_up");
+            format!("- {}: Delegated to {}", date, to)
+        }
+    };
+    note.append_log(&log);
+
+    let dest_path = move_note(note, "waiting", &config.status_dir("waiting")?)?;
+
+    println!("Delegated to {}: {}", to, dest_path.display());
+    if let Some(follow_up) = follow_up {
+        println!("Follow up: {}", follow_up);
     }
-    String::new()
+
+    Ok(())
 }
 
-fn collect_todos(dir: &Path, todos: &mut Vec<TodoItem>) -> Result<()> {
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
+pub fn cancel(file: &str, config: &Config) -> Result<()> {
+    let file_path = resolve_todo_file(file, config)?;
 
-        if path.is_file() && path.extension().map(|e| e == "md").unwrap_or(false) {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if let Some((status, project, due, created)) = parse_frontmatter(&content) {
-                    if !status.is_empty() && status != "done" && status != "canceled" {
-                        let title = extract_title(&content);
-                        todos.push(TodoItem {
-                            title,
-                            status,
-                            project,
-                            due,
-                            created,
-                            path,
-                        });
-                    }
-                }
-            }
+    let mut note = Note::load(&file_path)?;
+    if !note.frontmatter.is_present() {
+        anyhow::bail!("No frontmatter found");
+    }
+
+    let date = get_current_date(&config.general.date_format);
+    note.frontmatter.set("canceled", &date);
+    let title = note.title();
+    let dest_path = archive_note(note, "canceled", config)?;
+    record_closed_todo(&file_stem(&dest_path), &title, "canceled", config)?;
+
+    println!("Canceled: {}", file_path.display());
+    println!("Archived to: {}", dest_path.display());
+
+    Ok(())
+}
+
+/// Move a todo to `inbox`, `next`, `waiting`, `someday` (or any status with a
+/// configured directory), or into a project with `project:<name>`
+pub fn move_to(file: &str, to: &str, config: &Config) -> Result<()> {
+    let file_path = resolve_todo_file(file, config)?;
+
+    let mut note = Note::load(&file_path)?;
+    if !note.frontmatter.is_present() {
+        anyhow::bail!("No frontmatter found");
+    }
+
+    let dest_path = if let Some(project) = to.strip_prefix("project:") {
+        let (name, project_dir) = lookup_project(project, config)?;
+        note.frontmatter.set("project", &name);
+        move_note(note, "next", &project_dir)?
+    } else {
+        match to {
+            "done" => anyhow::bail!("Use `snail todo done` to complete a todo"),
+            "canceled" => anyhow::bail!("Use `snail todo cancel` to cancel a todo"),
+            _ => {}
         }
+        let dest_dir = config.status_dir(to).with_context(|| {
+            format!("Unknown destination: {} (expected inbox, next, waiting, someday or project:<name>)", to)
+        })?;
+        move_note(note, to, &dest_dir)?
+    };
+
+    println!("Moved: {}", file_path.display());
+    println!("To: {}", dest_path.display());
+
+    Ok(())
+}
+
+/// Turn a todo into a project: create the project, move the todo into it as
+/// its first task and create a todo for each unchecked item of its Project section
+pub fn promote(file: &str, name: Option<&str>, config: &Config) -> Result<()> {
+    let file_path = resolve_todo_file(file, config)?;
+
+    let note = Note::load(&file_path)?;
+    if !note.frontmatter.is_present() {
+        anyhow::bail!("No frontmatter found");
     }
+
+    let name = name.map(String::from).unwrap_or_else(|| note.title());
+    let promoted = promote_note(note, &name, config)?;
+
+    println!("Created project: {}", promoted.project_dir.display());
+    println!("README: {}", promoted.readme_path.display());
+    for step_path in &promoted.steps {
+        println!("Created todo: {}", step_path.display());
+    }
+    println!("Moved: {}", file_path.display());
+    println!("To: {}", promoted.dest_path.display());
+
     Ok(())
 }
 
-fn collect_todos_recursive(dir: &Path, todos: &mut Vec<TodoItem>) -> Result<()> {
-    if !dir.is_dir() {
-        return Ok(());
+/// What `promote_note` created
+pub struct Promoted {
+    pub name: String,
+    pub project_dir: PathBuf,
+    pub readme_path: PathBuf,
+    pub steps: Vec<PathBuf>,
+    pub dest_path: PathBuf,
+}
+
+/// Create a project named `name` from a note (shared by `todo promote` and
+/// `gtd process`). Prints nothing.
+pub fn promote_note(mut note: Note, name: &str, config: &Config) -> Result<Promoted> {
+    let name = sanitize_filename(name.trim());
+    if name.is_empty() {
+        anyhow::bail!("Project name is empty");
     }
 
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
+    let steps = project_section_steps(&note.body);
 
-        if path.is_dir() {
-            collect_todos_recursive(&path, todos)?;
-        } else if path.is_file() && path.extension().map(|e| e == "md").unwrap_or(false) {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if let Some((status, project, due, created)) = parse_frontmatter(&content) {
-                    if !status.is_empty() && status != "done" && status != "canceled" {
-                        let title = extract_title(&content);
-                        todos.push(TodoItem {
-                            title,
-                            status,
-                            project,
-                            due,
-                            created,
-                            path,
-                        });
-                    }
+    // Step files must not clash with each other or the todo itself;
+    // check before anything is created
+    let mut file_names: Vec<PathBuf> = note.path.file_name().map(PathBuf::from).into_iter().collect();
+    for (_, step) in &steps {
+        let file_name = todo_path(step, Path::new(""), config);
+        if file_names.contains(&file_name) {
+            anyhow::bail!("Step '{}' would overwrite {}", step, file_name.display());
+        }
+        file_names.push(file_name);
+    }
+
+    let (project_dir, readme_path) = create_project(&name, config)?;
+
+    // Each step becomes a todo; its checklist line links to the new file
+    let mut step_paths = Vec::new();
+    let mut lines: Vec<String> = note.body.split_inclusive('\n').map(String::from).collect();
+    for (line_index, step) in &steps {
+        let step_path = create_todo(step, &name, "next", &project_dir, config)?;
+        let stem = step_path.file_stem().and_then(|n| n.to_str()).unwrap_or_default();
+        let line = &mut lines[*line_index];
+        let ending = if line.ends_with('\n') { "\n" } else { "" };
+        *line = format!("{} [[{}]]{}", line.trim_end(), stem, ending);
+        step_paths.push(step_path);
+    }
+    note.body = lines.concat();
+
+    note.frontmatter.set("project", &name);
+    let dest_path = move_note(note, "next", &project_dir)?;
+
+    Ok(Promoted {
+        name,
+        project_dir,
+        readme_path,
+        steps: step_paths,
+        dest_path,
+    })
+}
+
+/// Unchecked `- [ ] step` lines of the `## ` section whose title contains
+/// "project" / "プロジェクト", as (line index, step text)
+fn project_section_steps(body: &str) -> Vec<(usize, String)> {
+    let mut steps = Vec::new();
+    let mut in_section = false;
+
+    for (i, line) in body.split_inclusive('\n').enumerate() {
+        if line.starts_with("## ") {
+            let heading = line.to_lowercase();
+            in_section = heading.contains("project") || heading.contains("プロジェクト");
+            continue;
+        }
+
+        if in_section {
+            if let Some(step) = line.trim_start().strip_prefix("- [ ]") {
+                let step = step.trim();
+                if !step.is_empty() {
+                    steps.push((i, step.to_string()));
                 }
             }
         }
     }
 
+    steps
+}
+
+/// Toggle the `n`th checkbox of a todo in place
+pub fn check(file: &str, n: usize, config: &Config) -> Result<()> {
+    let file_path = resolve_todo_file(file, config)?;
+
+    let mut note = Note::load(&file_path)?;
+    let (checked, text) = note.toggle_checkbox(n)?;
+    note.save()?;
+
+    let (done, total) = note.checklist();
+    let mark = if checked { "x" } else { " " };
+    println!("[{}] {} ({}/{})", mark, text, done, total);
+
     Ok(())
 }
 
-pub fn done(file: &str, config: &Config) -> Result<()> {
+/// Reopen an archived todo: restore its previous status and move it back to
+/// the directory it was archived from
+pub fn reopen(file: &str, config: &Config) -> Result<()> {
+    let file_path = if file.contains('/') || Path::new(file).exists() {
+        PathBuf::from(file)
+    } else {
+        find_archived_todo_file(file, config)?
+    };
+
+    if !file_path.exists() {
+        anyhow::bail!("File not found: {}", file);
+    }
+
+    let mut note = Note::load(&file_path)?;
+    if !note.frontmatter.is_present() {
+        anyhow::bail!("No frontmatter found");
+    }
+
+    // Todos archived before the origin was recorded go back to the inbox
+    let root_dir = config.root_dir()?;
+    let status = note.frontmatter.get_str("previous_status")
+        .unwrap_or_else(|| "inbox".to_string());
+    let archived_from = note.frontmatter.get_str("archived_from");
+    let dest_dir = match archived_from {
+        Some(ref dir) => root_dir.join(dir),
+        None => config.status_dir(&status)?,
+    };
+
+    for key in ["completed", "canceled", "archived_from", "previous_status"] {
+        note.frontmatter.remove(key);
+    }
+    let dest_path = move_note(note, &status, &dest_dir)?;
+
+    let dir_str = dest_dir.strip_prefix(&root_dir).unwrap_or(&dest_dir).display();
+    if archived_from.is_none() {
+        eprintln!("Warning: no archived_from recorded, restored to the {} directory ({})", status, dir_str);
+    }
+    println!("Reopened: {}", file_path.display());
+    println!("Restored to: {} (status: {})", dir_str, status);
+    println!("File: {}", dest_path.display());
+
+    Ok(())
+}
+
+/// Resolve a todo given as a path, a filename or a date prefix
+pub fn resolve_todo_file(file: &str, config: &Config) -> Result<PathBuf> {
     // If the file contains a path separator or exists as-is, use it directly
     // Otherwise, search in known directories
     let file_path = if file.contains('/') || Path::new(file).exists() {
@@ -281,34 +691,145 @@ pub fn done(file: &str, config: &Config) -> Result<()> {
         anyhow::bail!("File not found: {}", file);
     }
 
-    // Read the file content
-    let content = fs::read_to_string(&file_path)
-        .with_context(|| format!("Failed to read file: {:?}", file_path))?;
+    Ok(file_path)
+}
+
+/// Mark a note done and move it to the archive. Recurring todos get their
+/// next instance created. Returns the archived path and the new instance.
+pub fn archive_done(mut note: Note, config: &Config) -> Result<(PathBuf, Option<Note>)> {
+    // Build the next instance (with a free file name) before anything is moved
+    let next = next_occurrence(&note, config)?;
 
-    // Update frontmatter
     let date = get_current_date(&config.general.date_format);
-    let updated_content = update_frontmatter(&content, &date)?;
+    note.frontmatter.set("completed", &date);
+    let title = note.title();
+    let dest_path = archive_note(note, "done", config)?;
+
+    // The next instance may reuse the original's path, so it is saved after
+    // the move, but before anything else can fail
+    if let Some(ref next) = next {
+        next.save()?;
+    }
+    record_closed_todo(&file_stem(&dest_path), &title, "done", config)?;
 
-    // Write updated content back to file
-    fs::write(&file_path, &updated_content)
-        .with_context(|| format!("Failed to write file: {:?}", file_path))?;
+    Ok((dest_path, next))
+}
 
-    // Move to archive directory (99999_アーカイブ/99991_task)
-    let archive_dir = config.archive_dir()?.join("99991_task");
-    fs::create_dir_all(&archive_dir)
-        .with_context(|| format!("Failed to create archive directory: {:?}", archive_dir))?;
+/// File name without the `.md` extension, as used in `[[links]]`
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_string()
+}
 
-    let file_name = file_path.file_name()
+/// For a todo with a `repeat:` rule, build its next instance next to the
+/// original with the due date advanced and checkboxes reset (not saved yet)
+fn next_occurrence(note: &Note, config: &Config) -> Result<Option<Note>> {
+    let Some(rule) = note.frontmatter.get_str("repeat") else {
+        return Ok(None);
+    };
+    let repeat = Repeat::parse(&rule)?;
+    let date_format = &config.general.date_format;
+    let today = Local::now().date_naive();
+
+    // Advance from the due date (or today), skipping occurrences already past
+    let due = note.frontmatter.due_date(date_format).unwrap_or(today);
+    let due = repeat.next_due(due, today)
+        .ok_or_else(|| anyhow::anyhow!("Cannot compute next date for repeat rule: {}", rule))?
+        .format(date_format)
+        .to_string();
+
+    let mut next = note.clone();
+    let fm = &mut next.frontmatter;
+    fm.set_due(&due);
+    let date = get_current_date(date_format);
+    for key in ["date", "created"] {
+        if fm.contains_key(key) {
+            fm.set(key, &date);
+        }
+    }
+    fm.remove("completed");
+    next.reset_checkboxes();
+
+    // Same file name with today's date prefix
+    let file_name = note.path.file_name()
+        .and_then(|n| n.to_str())
         .ok_or_else(|| anyhow::anyhow!("Invalid file path"))?;
-    let dest_path = archive_dir.join(file_name);
+    let rest = file_name
+        .get(..date.len())
+        .filter(|prefix| NaiveDate::parse_from_str(prefix, date_format).is_ok())
+        .and_then(|_| file_name[date.len()..].strip_prefix('-'))
+        .unwrap_or(file_name);
+    let dir = note.path.parent().unwrap_or(Path::new("."));
+    next.path = free_path(&dir.join(format!("{}-{}", date, rest)), &note.path);
+
+    Ok(Some(next))
+}
 
-    fs::rename(&file_path, &dest_path)
-        .with_context(|| format!("Failed to move file to archive: {:?}", dest_path))?;
+/// `path`, or `name-2.md`, `name-3.md`, ... when it is taken. `moving` is
+/// about to be moved away, so its path counts as free.
+fn free_path(path: &Path, moving: &Path) -> PathBuf {
+    let is_free = |p: &Path| p == moving || !p.exists();
+    if is_free(path) {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    let extension = path.extension().and_then(|e| e.to_str());
+    (2..)
+        .map(|n| {
+            let name = match extension {
+                Some(ext) => format!("{}-{}.{}", stem, n, ext),
+                None => format!("{}-{}", stem, n),
+            };
+            path.with_file_name(name)
+        })
+        .find(|p| is_free(p))
+        .unwrap_or_else(|| path.to_path_buf())
+}
 
-    println!("Marked as done: {}", file_path.display());
-    println!("Archived to: {}", dest_path.display());
+/// File a note under a closed status (done, canceled, ...), recording where
+/// it came from so `todo reopen` can put it back. A name already taken in the
+/// archive (e.g. a repeating todo done twice a day) gets a `-2` suffix.
+pub fn archive_note(mut note: Note, status: &str, config: &Config) -> Result<PathBuf> {
+    let root_dir = config.root_dir()?;
+    if let Some(dir) = note.path.parent() {
+        let original_dir = dir.strip_prefix(&root_dir).unwrap_or(dir);
+        note.frontmatter.set("archived_from", &original_dir.display().to_string());
+    }
+    let previous_status = note.frontmatter.status().unwrap_or_default();
+    note.frontmatter.set("previous_status", &previous_status);
 
-    Ok(())
+    let file_name = note.path.file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid file path"))?;
+    let dest_path = free_path(&config.status_dir(status)?.join(file_name), &note.path);
+    move_note_to(note, status, &dest_path)
+}
+
+/// Set `status:` and move the note into `dest_dir`. Returns the new path.
+pub fn move_note(note: Note, status: &str, dest_dir: &Path) -> Result<PathBuf> {
+    let file_name = note.path.file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid file path"))?;
+    let dest_path = dest_dir.join(file_name);
+    if dest_path != note.path && dest_path.exists() {
+        anyhow::bail!("File already exists: {}", dest_path.display());
+    }
+    move_note_to(note, status, &dest_path)
+}
+
+fn move_note_to(mut note: Note, status: &str, dest_path: &Path) -> Result<PathBuf> {
+    if let Some(dest_dir) = dest_path.parent() {
+        fs::create_dir_all(dest_dir)
+            .with_context(|| format!("Failed to create directory: {:?}", dest_dir))?;
+    }
+
+    note.frontmatter.set("status", status);
+    note.save()?;
+
+    fs::rename(&note.path, dest_path)
+        .with_context(|| format!("Failed to move file to: {:?}", dest_path))?;
+
+    Ok(dest_path.to_path_buf())
 }
 
 /// Check if the string is a date format (YYYY-MM-DD)
@@ -334,6 +855,8 @@ fn find_todo_file(query: &str, config: &Config) -> Result<PathBuf> {
     let search_dirs = vec![
         config.inbox_dir()?,
         config.next_dir()?,
+        config.waiting_dir()?,
+        config.someday_dir()?,
     ];
 
     for dir in search_dirs {
@@ -348,6 +871,23 @@ fn find_todo_file(query: &str, config: &Config) -> Result<PathBuf> {
         search_recursive(&project_dir, query, is_date, &mut candidates)?;
     }
 
+    select_candidate(query, candidates)
+}
+
+/// Search the archive (recursively) with the same matching as `find_todo_file`
+fn find_archived_todo_file(query: &str, config: &Config) -> Result<PathBuf> {
+    let mut candidates: Vec<PathBuf> = Vec::new();
+
+    let archive_dir = config.archive_dir()?;
+    if archive_dir.exists() {
+        search_recursive(&archive_dir, query, is_date_format(query), &mut candidates)?;
+    }
+
+    select_candidate(query, candidates)
+}
+
+/// Pick one of the matching files, prompting when there are several
+fn select_candidate(query: &str, mut candidates: Vec<PathBuf>) -> Result<PathBuf> {
     match candidates.len() {
         0 => anyhow::bail!("File not found: {}", query),
         1 => Ok(candidates.remove(0)),
@@ -422,48 +962,53 @@ fn search_recursive(dir: &Path, query: &str, is_date: bool, candidates: &mut Vec
     Ok(())
 }
 
-fn update_frontmatter(content: &str, completed_date: &str) -> Result<String> {
-    let lines: Vec<&str> = content.lines().collect();
-
-    // Find frontmatter boundaries
-    if lines.is_empty() || lines[0] != "---" {
-        anyhow::bail!("No frontmatter found");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_path_skips_taken_names() {
+        let dir = std::env::temp_dir().join(format!("snail-free-path-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("2026-10-18-Water plants.md");
+        let original = dir.join("2026-10-11-Water plants.md");
+
+        assert_eq!(free_path(&path, &original), path);
+        fs::write(&path, "").unwrap();
+        assert_eq!(free_path(&path, &original), dir.join("2026-10-18-Water plants-2.md"));
+        assert_eq!(free_path(&path, &path), path);
+        fs::write(dir.join("2026-10-18-Water plants-2.md"), "").unwrap();
+        assert_eq!(free_path(&path, &original), dir.join("2026-10-18-Water plants-3.md"));
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 
-    let mut end_index = None;
-    for (i, line) in lines.iter().enumerate().skip(1) {
-        if *line == "---" {
-            end_index = Some(i);
-            break;
-        }
+    #[test]
+    fn repeating_todo_can_be_completed_twice_a_day() {
+        let root = std::env::temp_dir().join(format!("snail-repeat-{}", std::process::id()));
+        let mut config = Config::default();
+        config.general.root_dir = root.display().to_string();
+        let inbox = config.inbox_dir().unwrap();
+        fs::create_dir_all(&inbox).unwrap();
+
+        let date = get_current_date(&config.general.date_format);
+        let path = inbox.join(format!("{}-Water plants.md", date));
+        fs::write(&path, "---\nstatus: next\nrepeat: daily\n---\n# Water plants\n\n- [x] kitchen\n").unwrap();
+
+        let (first, next) = archive_done(Note::load(&path).unwrap(), &config).unwrap();
+        let next = next.unwrap();
+        assert_eq!(next.path, path);
+        assert!(fs::read_to_string(&path).unwrap().contains("- [ ] kitchen"));
+
+        let (second, next) = archive_done(next, &config).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(second.file_name().unwrap().to_str().unwrap(), format!("{}-Water plants-2.md", date));
+        assert!(first.exists() && second.exists() && next.unwrap().path.exists());
+
+        let report = fs::read_to_string(inbox.join(format!("{}-daily_report.md", date))).unwrap();
+        assert!(report.contains(&format!("[[{}-Water plants]]", date)));
+        assert!(report.contains(&format!("[[{}-Water plants-2]]", date)));
+
+        fs::remove_dir_all(&root).unwrap();
     }
-
-    let end_index = end_index.ok_or_else(|| anyhow::anyhow!("Invalid frontmatter"))?;
-
-    // Update status and add completed date
-    let mut new_lines: Vec<String> = Vec::new();
-    let mut completed_added = false;
-
-    for (i, line) in lines.iter().enumerate() {
-        if i > 0 && i < end_index {
-            if line.starts_with("status:") {
-                new_lines.push("status: done".to_string());
-            } else if line.starts_with("completed:") {
-                new_lines.push(format!("completed: {}", completed_date));
-                completed_added = true;
-            } else {
-                new_lines.push(line.to_string());
-            }
-        } else if i == end_index {
-            // Add completed field before closing --- if not already present
-            if !completed_added {
-                new_lines.push(format!("completed: {}", completed_date));
-            }
-            new_lines.push(line.to_string());
-        } else {
-            new_lines.push(line.to_string());
-        }
-    }
-
-    Ok(new_lines.join("\n"))
 }
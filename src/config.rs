@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -10,22 +11,63 @@ pub struct Config {
     pub directories: DirectoryConfig,
     #[serde(default)]
     pub gtd: GtdConfig,
+    #[serde(default)]
+    pub daily_report: DailyReportConfig,
+}
+
+/// Heading text (without `## `) of each daily report section
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct DailyReportConfig {
+    pub todo: String,
+    pub done: String,
+    pub canceled: String,
+    pub memo: String,
+    pub log: String,
+}
+
+impl Default for DailyReportConfig {
+    fn default() -> Self {
+        Self {
+            todo: "TODO".to_string(),
+            done: "Done".to_string(),
+            canceled: "Canceled".to_string(),
+            memo: "Memo".to_string(),
+            log: "Log".to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct GtdConfig {
     #[serde(default = "default_braindump_duration")]
     pub braindump_duration_mins: u64,
+    /// Weekly report file name; supports {{iso_year}}, {{week}}, {{start_date}} and {{end_date}}
+    #[serde(default = "default_weekly_report_filename")]
+    pub weekly_report_filename: String,
+    /// Record done / canceled todos in today's daily report
+    #[serde(default = "default_record_completed")]
+    pub record_completed: bool,
 }
 
 fn default_braindump_duration() -> u64 {
     10
 }
 
+fn default_weekly_report_filename() -> String {
+    "{{iso_year}}-W{{week}}-weekly_report.md".to_string()
+}
+
+fn default_record_completed() -> bool {
+    true
+}
+
 impl Default for GtdConfig {
     fn default() -> Self {
         Self {
             braindump_duration_mins: default_braindump_duration(),
+            weekly_report_filename: default_weekly_report_filename(),
+            record_completed: default_record_completed(),
         }
     }
 }
@@ -45,23 +87,45 @@ pub struct TemplateConfig {
     pub todo: String,
     pub project: String,
     pub daily_report: String,
+    #[serde(default = "default_weekly_report_template")]
+    pub weekly_report: String,
+}
+
+fn default_weekly_report_template() -> String {
+    "~/.config/snail-cli/templates/weekly_report.md".to_string()
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DirectoryConfig {
     pub inbox: String,
     pub next: String,
+    #[serde(default = "default_waiting")]
+    pub waiting: String,
     pub someday: String,
     pub project: String,
     pub archive: String,
     #[serde(default = "default_weekly_report")]
     pub weekly_report: String,
+    #[serde(default = "default_monthly_report")]
+    pub monthly_report: String,
+    /// Directory (relative to root_dir) each `status:` is filed into, e.g.
+    /// `done = "99999_アーカイブ/99991_task"`. Unlisted statuses use the defaults.
+    #[serde(default)]
+    pub status: BTreeMap<String, String>,
+}
+
+fn default_waiting() -> String {
+    "00200_WAITING".to_string()
 }
 
 fn default_weekly_report() -> String {
     "00700_メモ/00708_report/00782_WEEKLY".to_string()
 }
 
+fn default_monthly_report() -> String {
+    "00700_メモ/00708_report/00783_MONTHLY".to_string()
+}
+
 impl Config {
     pub fn load() -> Result<Self> {
         let config_path = Self::config_path()?;
@@ -84,11 +148,6 @@ impl Config {
         Ok(PathBuf::from(config_dir.as_ref()).join("config.toml"))
     }
 
-    pub fn template_dir() -> Result<PathBuf> {
-        let config_dir = shellexpand::tilde("~/.config/snail-cli");
-        Ok(PathBuf::from(config_dir.as_ref()).join("templates"))
-    }
-
     pub fn root_dir(&self) -> Result<PathBuf> {
         let expanded = shellexpand::tilde(&self.general.root_dir);
         Ok(PathBuf::from(expanded.as_ref()))
@@ -102,6 +161,10 @@ impl Config {
         Ok(self.root_dir()?.join(&self.directories.next))
     }
 
+    pub fn waiting_dir(&self) -> Result<PathBuf> {
+        Ok(self.root_dir()?.join(&self.directories.waiting))
+    }
+
     pub fn someday_dir(&self) -> Result<PathBuf> {
         Ok(self.root_dir()?.join(&self.directories.someday))
     }
@@ -118,6 +181,28 @@ impl Config {
         Ok(self.root_dir()?.join(&self.directories.weekly_report))
     }
 
+    pub fn monthly_report_dir(&self) -> Result<PathBuf> {
+        Ok(self.root_dir()?.join(&self.directories.monthly_report))
+    }
+
+    /// Directory a note with the given `status:` is filed into
+    pub fn status_dir(&self, status: &str) -> Result<PathBuf> {
+        if let Some(dir) = self.directories.status.get(status) {
+            return Ok(self.root_dir()?.join(dir));
+        }
+
+        match status {
+            "inbox" => self.inbox_dir(),
+            "next" => self.next_dir(),
+            "waiting" => self.waiting_dir(),
+            "someday" => self.someday_dir(),
+            "done" | "canceled" => Ok(self.archive_dir()?.join("99991_task")),
+            "reference" => Ok(self.archive_dir()?.join("99992_reference")),
+            "trash" => Ok(self.archive_dir()?.join("99990_trash")),
+            _ => anyhow::bail!("No directory configured for status: {}", status),
+        }
+    }
+
     pub fn get_template_path(&self, template_name: &str) -> Result<PathBuf> {
         let template_path = match template_name {
             "base" => &self.templates.base,
@@ -125,6 +210,7 @@ impl Config {
             "todo" => &self.templates.todo,
             "project" => &self.templates.project,
             "daily_report" => &self.templates.daily_report,
+            "weekly_report" => &self.templates.weekly_report,
             _ => anyhow::bail!("Unknown template: {}", template_name),
         };
 
@@ -148,16 +234,21 @@ impl Default for Config {
                 todo: "~/.config/snail-cli/templates/todo.md".to_string(),
                 project: "~/.config/snail-cli/templates/project.md".to_string(),
                 daily_report: "~/.config/snail-cli/templates/daily_report.md".to_string(),
+                weekly_report: default_weekly_report_template(),
             },
             directories: DirectoryConfig {
                 inbox: "00000_INBOX".to_string(),
                 next: "00100_NEXTACTION".to_string(),
+                waiting: default_waiting(),
                 someday: "00500_いつかやる".to_string(),
                 project: "00800_プロジェクト".to_string(),
                 archive: "99999_アーカイブ".to_string(),
                 weekly_report: "00700_メモ/00708_report/00782_WEEKLY".to_string(),
+                monthly_report: "00700_メモ/00708_report/00783_MONTHLY".to_string(),
+                status: BTreeMap::new(),
             },
             gtd: GtdConfig::default(),
+            daily_report: DailyReportConfig::default(),
         }
     }
 }
@@ -0,0 +1,181 @@
+use chrono::{Datelike, Days, Duration, Months, NaiveDate, Weekday};
+
+/// Parse a date expression relative to `today`.
+///
+/// Accepted forms:
+/// - `today`, `tomorrow`, `yesterday`
+/// - weekdays (`fri`, `friday`, `next fri`): the next such day after today
+/// - `+3d`, `-1w`, `+2m`, `+1y`, `in 3 days`, `in 2 weeks`
+/// - `next week`, `next month`, `next year` (one week / month / year from today)
+/// - `eow` / `end of week` (Sunday), `eom` / `end of month`
+/// - a date in `date_format`, or ISO `YYYY-MM-DD`
+pub fn parse_date(expr: &str, today: NaiveDate, date_format: &str) -> Option<NaiveDate> {
+    let expr = expr.trim();
+    let lower = expr.to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+
+    match words.as_slice() {
+        ["today"] => return Some(today),
+        ["tomorrow"] => return today.checked_add_days(Days::new(1)),
+        ["yesterday"] => return today.checked_sub_days(Days::new(1)),
+        ["eow"] | ["end", "of", "week"] => {
+            let days_left = 6 - today.weekday().num_days_from_monday() as u64;
+            return today.checked_add_days(Days::new(days_left));
+        }
+        ["eom"] | ["end", "of", "month"] => {
+            let first = today.with_day(1)?;
+            return first.checked_add_months(Months::new(1))?.checked_sub_days(Days::new(1));
+        }
+        ["next", unit] => {
+            if let Some(weekday) = parse_weekday(unit) {
+                return Some(next_weekday(today, weekday));
+            }
+            return shift(today, 1, unit, true);
+        }
+        [day] if parse_weekday(day).is_some() => {
+            return Some(next_weekday(today, parse_weekday(day)?));
+        }
+        ["in", amount, unit] => return shift(today, amount.parse().ok()?, unit, true),
+        _ => {}
+    }
+
+    if let Some(sign) = lower.chars().next().filter(|c| *c == '+' || *c == '-') {
+        let rest = &lower[1..];
+        let unit_start = rest.find(|c: char| !c.is_ascii_digit())?;
+        let amount: u32 = rest[..unit_start].parse().ok()?;
+        return shift(today, amount, &rest[unit_start..], sign == '+');
+    }
+
+    NaiveDate::parse_from_str(expr, date_format)
+        .or_else(|_| NaiveDate::parse_from_str(expr, "%Y-%m-%d"))
+        .ok()
+}
+
+/// Move `amount` days / weeks / months / years from `date`; `None` when the
+/// result is out of range
+fn shift(date: NaiveDate, amount: u32, unit: &str, forward: bool) -> Option<NaiveDate> {
+    let days = |n: u64| {
+        if forward {
+            date.checked_add_days(Days::new(n))
+        } else {
+            date.checked_sub_days(Days::new(n))
+        }
+    };
+    let months = |n: u32| {
+        if forward {
+            date.checked_add_months(Months::new(n))
+        } else {
+            date.checked_sub_months(Months::new(n))
+        }
+    };
+
+    match unit.trim_end_matches('s') {
+        "d" | "day" => days(amount as u64),
+        "w" | "week" => days(amount as u64 * 7),
+        "m" | "month" => months(amount),
+        "y" | "year" => months(amount.checked_mul(12)?),
+        _ => None,
+    }
+}
+
+/// The first `weekday` strictly after `today`
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let mut date = today + Duration::days(1);
+    while date.weekday() != weekday {
+        date += Duration::days(1);
+    }
+    date
+}
+
+pub fn parse_weekday(s: &str) -> Option<Weekday> {
+    let weekday = match s {
+        "monday" | "mon" | "mo" => Weekday::Mon,
+        "tuesday" | "tue" | "tu" => Weekday::Tue,
+        "wednesday" | "wed" | "we" => Weekday::Wed,
+        "thursday" | "thu" | "th" => Weekday::Thu,
+        "friday" | "fri" | "fr" => Weekday::Fri,
+        "saturday" | "sat" | "sa" => Weekday::Sat,
+        "sunday" | "sun" | "su" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn parses_expressions_relative_to_today() {
+        // A Saturday at the end of a month
+        let today = date("2026-01-31");
+        let cases = [
+            ("today", "2026-01-31"),
+            ("tomorrow", "2026-02-01"),
+            ("Yesterday", "2026-01-30"),
+            ("fri", "2026-02-06"),
+            ("Friday", "2026-02-06"),
+            ("next fri", "2026-02-06"),
+            ("sat", "2026-02-07"),
+            ("+3d", "2026-02-03"),
+            ("-1w", "2026-01-24"),
+            ("+2m", "2026-03-31"),
+            ("+1y", "2027-01-31"),
+            ("in 2 weeks", "2026-02-14"),
+            ("in 1 day", "2026-02-01"),
+            ("next week", "2026-02-07"),
+            ("next month", "2026-02-28"),
+            ("next year", "2027-01-31"),
+            ("eow", "2026-02-01"),
+            ("end of month", "2026-01-31"),
+            ("2026-11-01", "2026-11-01"),
+            (" 2026-11-01 ", "2026-11-01"),
+        ];
+        for (expr, expected) in cases {
+            assert_eq!(parse_date(expr, today, "%Y-%m-%d"), Some(date(expected)), "{}", expr);
+        }
+    }
+
+    #[test]
+    fn end_of_month_in_a_leap_year() {
+        let today = date("2028-02-10");
+        assert_eq!(parse_date("eom", today, "%Y-%m-%d"), Some(date("2028-02-29")));
+        assert_eq!(parse_date("next month", date("2028-01-31"), "%Y-%m-%d"), Some(date("2028-02-29")));
+    }
+
+    #[test]
+    fn explicit_dates_use_the_configured_format_or_iso() {
+        let today = date("2026-10-18");
+        assert_eq!(parse_date("2026/11/01", today, "%Y/%m/%d"), Some(date("2026-11-01")));
+        assert_eq!(parse_date("2026-11-01", today, "%Y/%m/%d"), Some(date("2026-11-01")));
+    }
+
+    #[test]
+    fn rejects_unknown_expressions() {
+        let today = date("2026-10-18");
+        for expr in ["", "someday", "+3x", "+d", "in two days", "next decade", "2026-13-01", "-日"] {
+            assert_eq!(parse_date(expr, today, "%Y-%m-%d"), None, "{}", expr);
+        }
+    }
+
+    #[test]
+    fn out_of_range_expressions_are_rejected() {
+        let today = date("2026-10-18");
+        let cases = [
+            "+99999999d",
+            "-99999999d",
+            "+4294967295w",
+            "+4294967295m",
+            "+400000000y",
+            "in 4294967295 years",
+            "+99999999999999999999d",
+        ];
+        for expr in cases {
+            assert_eq!(parse_date(expr, today, "%Y-%m-%d"), None, "{}", expr);
+        }
+    }
+}
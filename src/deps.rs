@@ -0,0 +1,322 @@
+use gray_matter::Pod;
+use std::path::Path;
+use crate::note::Note;
+
+/// Dependency graph built from the `depends_on:` field of todos.
+///
+/// A reference matches a todo by its `id:`, its file stem or its file name
+/// (`[[wiki links]]` are accepted). A todo is blocked while any dependency
+/// is still open (not filed under a closed status).
+pub struct DependencyGraph<'a> {
+    notes: Vec<&'a Note>,
+    /// Resolved dependencies of each note
+    edges: Vec<Vec<usize>>,
+    /// (note, reference) pairs that match no todo
+    dangling: Vec<(usize, String)>,
+}
+
+impl<'a> DependencyGraph<'a> {
+    pub fn new(notes: impl IntoIterator<Item = &'a Note>) -> Self {
+        let notes: Vec<&Note> = notes.into_iter().collect();
+        let mut edges = vec![Vec::new(); notes.len()];
+        let mut dangling = Vec::new();
+
+        for (i, note) in notes.iter().enumerate() {
+            for reference in dependency_refs(note) {
+                let matches: Vec<usize> = (0..notes.len())
+                    .filter(|&j| j != i && matches_ref(notes[j], &reference))
+                    .collect();
+                if matches.is_empty() {
+                    dangling.push((i, reference));
+                }
+                for j in matches {
+                    if !edges[i].contains(&j) {
+                        edges[i].push(j);
+                    }
+                }
+            }
+        }
+
+        Self { notes, edges, dangling }
+    }
+
+    fn index_of(&self, path: &Path) -> Option<usize> {
+        self.notes.iter().position(|n| n.path == path).or_else(|| {
+            // The same file given as a relative path or through a symlink
+            let canonical = path.canonicalize().ok()?;
+            self.notes
+                .iter()
+                .position(|n| n.path.canonicalize().ok().as_deref() == Some(canonical.as_path()))
+        })
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&'a Note> {
+        self.index_of(path).map(|i| self.notes[i])
+    }
+
+    /// Open todos the note at `path` is waiting on
+    pub fn blockers(&self, path: &Path) -> Vec<&'a Note> {
+        let Some(i) = self.index_of(path) else {
+            return Vec::new();
+        };
+        self.edges[i]
+            .iter()
+            .map(|&j| self.notes[j])
+            .filter(|n| is_open(n))
+            .collect()
+    }
+
+    /// Todos that list the note at `path` as a dependency
+    pub fn dependents(&self, path: &Path) -> Vec<&'a Note> {
+        let Some(i) = self.index_of(path) else {
+            return Vec::new();
+        };
+        (0..self.notes.len())
+            .filter(|&j| self.edges[j].contains(&i))
+            .map(|j| self.notes[j])
+            .collect()
+    }
+
+    /// Order the given notes so that dependencies come first. Notes caught
+    /// in a cycle keep their original order at the end.
+    pub fn order(&self, paths: &[&Path]) -> Vec<&'a Note> {
+        let subset: Vec<usize> = paths.iter().filter_map(|p| self.index_of(p)).collect();
+        let mut ordered: Vec<usize> = Vec::new();
+        let mut remaining = subset.clone();
+
+        loop {
+            let ready = remaining.iter().position(|&i| {
+                self.edges[i]
+                    .iter()
+                    .all(|j| !subset.contains(j) || ordered.contains(j))
+            });
+            match ready {
+                Some(pos) => ordered.push(remaining.remove(pos)),
+                None => break,
+            }
+        }
+        ordered.extend(remaining);
+
+        ordered.into_iter().map(|i| self.notes[i]).collect()
+    }
+
+    /// Dependency cycles, each as a list of notes (first note repeated at the end)
+    pub fn cycles(&self) -> Vec<Vec<&'a Note>> {
+        // 0 = unvisited, 1 = on the current path, 2 = finished
+        let mut state = vec![0u8; self.notes.len()];
+        let mut stack: Vec<usize> = Vec::new();
+        let mut cycles: Vec<Vec<usize>> = Vec::new();
+
+        for start in 0..self.notes.len() {
+            if state[start] == 0 {
+                self.find_cycles(start, &mut state, &mut stack, &mut cycles);
+            }
+        }
+
+        cycles
+            .into_iter()
+            .map(|cycle| cycle.into_iter().map(|i| self.notes[i]).collect())
+            .collect()
+    }
+
+    fn find_cycles(&self, i: usize, state: &mut [u8], stack: &mut Vec<usize>, cycles: &mut Vec<Vec<usize>>) {
+        state[i] = 1;
+        stack.push(i);
+
+        for &j in &self.edges[i] {
+            match state[j] {
+                0 => self.find_cycles(j, state, stack, cycles),
+                1 => {
+                    let pos = stack.iter().position(|&k| k == j).unwrap_or(0);
+                    let mut cycle = stack[pos..].to_vec();
+                    cycle.push(j);
+                    cycles.push(cycle);
+                }
+                _ => {}
+            }
+        }
+
+        stack.pop();
+        state[i] = 2;
+    }
+
+    /// Print dangling references and cycles to stderr
+    pub fn report_problems(&self) {
+        for (i, reference) in &self.dangling {
+            eprintln!(
+                "Warning: {} depends on '{}', which matches no todo",
+                self.notes[*i].path.display(),
+                reference
+            );
+        }
+        for cycle in self.cycles() {
+            let names: Vec<String> = cycle.iter().map(|n| n.stem()).collect();
+            eprintln!("Warning: dependency cycle: {}", names.join(" -> "));
+        }
+    }
+}
+
+/// References listed in `depends_on:`: a list, a comma separated value, or
+/// wiki links (`[[a]]` parses as a nested list in YAML)
+fn dependency_refs(note: &Note) -> Vec<String> {
+    let mut refs = Vec::new();
+    if let Some(value) = note.frontmatter.get("depends_on") {
+        flatten_refs(value, &mut refs);
+    }
+    refs
+}
+
+fn flatten_refs(value: &Pod, refs: &mut Vec<String>) {
+    match value {
+        Pod::Array(items) => {
+            for item in items {
+                flatten_refs(item, refs);
+            }
+        }
+        Pod::String(s) => {
+            for part in s.split(',') {
+                let part = part.trim();
+                let part = part
+                    .strip_prefix("[[")
+                    .and_then(|p| p.strip_suffix("]]"))
+                    .unwrap_or(part);
+                if !part.is_empty() {
+                    refs.push(part.to_string());
+                }
+            }
+        }
+        Pod::Integer(i) => refs.push(i.to_string()),
+        _ => {}
+    }
+}
+
+fn matches_ref(note: &Note, reference: &str) -> bool {
+    let stem = note.stem();
+    let reference = reference.strip_suffix(".md").unwrap_or(reference);
+    stem == reference || note.frontmatter.get_str("id").as_deref() == Some(reference)
+}
+
+pub fn is_open(note: &Note) -> bool {
+    note.frontmatter
+        .status()
+        .map(|s| !is_closed_status(&s))
+        .unwrap_or(false)
+}
+
+/// Statuses that file a note in the archive: done, canceled, and `reference`
+/// / `trash` from `gtd process`
+pub fn is_closed_status(status: &str) -> bool {
+    matches!(status, "done" | "canceled" | "reference" | "trash")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn todo(stem: &str, frontmatter: &str) -> Note {
+        let content = format!("---\n{}\n---\n# {}\n", frontmatter, stem);
+        Note::parse(PathBuf::from(format!("{}.md", stem)), &content)
+    }
+
+    fn stems(notes: &[&Note]) -> Vec<String> {
+        notes.iter().map(|n| n.stem()).collect()
+    }
+
+    #[test]
+    fn resolves_ids_stems_file_names_and_links() {
+        let notes = [
+            todo("a", "status: next\ndepends_on: [b, c.md, \"[[d]]\", x1]"),
+            todo("b", "status: next"),
+            todo("c", "status: waiting"),
+            todo("d", "status: inbox"),
+            todo("e", "status: next\nid: x1"),
+        ];
+        let graph = DependencyGraph::new(&notes);
+        assert_eq!(stems(&graph.blockers(Path::new("a.md"))), vec!["b", "c", "d", "e"]);
+        assert_eq!(stems(&graph.dependents(Path::new("e.md"))), vec!["a"]);
+        assert!(graph.dangling.is_empty());
+    }
+
+    #[test]
+    fn wiki_links_and_comma_lists() {
+        let notes = [
+            todo("a", "status: next\ndepends_on: [[b]]"),
+            todo("b", "status: next\ndepends_on: c, d"),
+            todo("c", "status: next"),
+            todo("d", "status: next"),
+        ];
+        let graph = DependencyGraph::new(&notes);
+        assert_eq!(stems(&graph.blockers(Path::new("a.md"))), vec!["b"]);
+        assert_eq!(stems(&graph.blockers(Path::new("b.md"))), vec!["c", "d"]);
+    }
+
+    #[test]
+    fn closed_dependencies_do_not_block() {
+        let notes = [
+            todo("a", "status: next\ndepends_on: [b, c, d, e, f]"),
+            todo("b", "status: done"),
+            todo("c", "status: canceled"),
+            todo("d", "status: trash"),
+            todo("e", "status: reference"),
+            todo("f", "status: someday"),
+        ];
+        let graph = DependencyGraph::new(&notes);
+        assert_eq!(stems(&graph.blockers(Path::new("a.md"))), vec!["f"]);
+    }
+
+    #[test]
+    fn dangling_references_are_kept() {
+        let notes = [todo("a", "status: next\ndepends_on: [b, missing]"), todo("b", "status: next")];
+        let graph = DependencyGraph::new(&notes);
+        assert_eq!(graph.dangling, vec![(0, "missing".to_string())]);
+        assert_eq!(stems(&graph.blockers(Path::new("a.md"))), vec!["b"]);
+        assert!(graph.blockers(Path::new("unknown.md")).is_empty());
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let notes = [
+            todo("a", "status: next\ndepends_on: b"),
+            todo("b", "status: next\ndepends_on: c"),
+            todo("c", "status: next\ndepends_on: a"),
+            todo("d", "status: next\ndepends_on: d"),
+            todo("e", "status: next\ndepends_on: a"),
+        ];
+        let graph = DependencyGraph::new(&notes);
+        let cycles: Vec<Vec<String>> = graph.cycles().iter().map(|c| stems(c)).collect();
+        assert_eq!(cycles, vec![vec!["a", "b", "c", "a"]]);
+
+        let acyclic = [todo("a", "status: next\ndepends_on: b"), todo("b", "status: next")];
+        assert!(DependencyGraph::new(&acyclic).cycles().is_empty());
+    }
+
+    #[test]
+    fn orders_dependencies_first() {
+        let notes = [
+            todo("deploy", "status: next\ndepends_on: [build, test]"),
+            todo("test", "status: next\ndepends_on: build"),
+            todo("build", "status: next"),
+            todo("docs", "status: next"),
+        ];
+        let graph = DependencyGraph::new(&notes);
+        let paths: Vec<PathBuf> = notes.iter().map(|n| n.path.clone()).collect();
+        let paths: Vec<&Path> = paths.iter().map(|p| p.as_path()).collect();
+        assert_eq!(stems(&graph.order(&paths)), vec!["build", "test", "deploy", "docs"]);
+
+        // Dependencies outside the subset do not hold a note back
+        assert_eq!(stems(&graph.order(&[Path::new("deploy.md"), Path::new("test.md")])), vec!["test", "deploy"]);
+    }
+
+    #[test]
+    fn cycles_go_last_in_order() {
+        let notes = [
+            todo("a", "status: next\ndepends_on: b"),
+            todo("b", "status: next\ndepends_on: a"),
+            todo("c", "status: next"),
+        ];
+        let graph = DependencyGraph::new(&notes);
+        let paths = [Path::new("a.md"), Path::new("b.md"), Path::new("c.md")];
+        assert_eq!(stems(&graph.order(&paths)), vec!["c", "a", "b"]);
+    }
+}
@@ -1,6 +1,13 @@
 mod cli;
 mod commands;
 mod config;
+mod dates;
+mod deps;
+mod markdown;
+mod note;
+mod output;
+mod query;
+mod repeat;
 mod utils;
 
 use anyhow::Result;
@@ -17,30 +24,51 @@ fn main() -> Result<()> {
             MemoAction::New { title, no_edit } => {
                 commands::memo::new(&title, no_edit, &config)?;
             }
-            MemoAction::List => {
-                commands::memo::list(&config)?;
+            MemoAction::List { filter, archive, output } => {
+                commands::memo::list(&filter, archive, &output, &config)?;
             }
-            MemoAction::Search { keyword } => {
-                commands::memo::search(&keyword, &config)?;
+            MemoAction::Search { keyword, output } => {
+                commands::memo::search(&keyword, &output, &config)?;
             }
         },
         Commands::Todo { action } => match action {
-            TodoAction::New { title, project, no_edit } => {
-                commands::todo::new(&title, project.as_deref(), no_edit, &config)?;
+            TodoAction::New { title, project, due, no_edit } => {
+                commands::todo::new(&title, project.as_deref(), due.as_deref(), no_edit, &config)?;
             }
-            TodoAction::List { filter } => {
-                commands::todo::list(&filter, &config)?;
+            TodoAction::List { filter, archive, hide_blocked, deferred, output } => {
+                commands::todo::list(&filter, archive, hide_blocked, deferred, &output, &config)?;
             }
             TodoAction::Done { file } => {
                 commands::todo::done(&file, &config)?;
             }
+            TodoAction::Move { file, to } => {
+                commands::todo::move_to(&file, &to, &config)?;
+            }
+            TodoAction::Delegate { file, to, follow_up } => {
+                commands::todo::delegate(&file, &to, follow_up.as_deref(), &config)?;
+            }
+            TodoAction::Cancel { file } => {
+                commands::todo::cancel(&file, &config)?;
+            }
+            TodoAction::Promote { file, name } => {
+                commands::todo::promote(&file, name.as_deref(), &config)?;
+            }
+            TodoAction::Due { file, date } => {
+                commands::todo::due(&file, &date, &config)?;
+            }
+            TodoAction::Check { file, n } => {
+                commands::todo::check(&file, n, &config)?;
+            }
+            TodoAction::Reopen { file } => {
+                commands::todo::reopen(&file, &config)?;
+            }
         },
         Commands::Project { action } => match action {
             ProjectAction::New { name, no_edit } => {
                 commands::project::new(&name, no_edit, &config)?;
             }
-            ProjectAction::List => {
-                commands::project::list(&config)?;
+            ProjectAction::List { output } => {
+                commands::project::list(&output, &config)?;
             }
             ProjectAction::Show { name } => {
                 commands::project::show(&name, &config)?;
@@ -48,12 +76,24 @@ fn main() -> Result<()> {
         },
         Commands::Gtd { action } => match action {
             GtdAction::Today { action } => match action {
-                TodayAction::List => {
-                    commands::gtd::today_list(&config)?;
+                TodayAction::List { output } => {
+                    commands::gtd::today_list(&output, &config)?;
+                }
+                TodayAction::Start => {
+                    commands::gtd::today_start(&config)?;
                 }
                 TodayAction::Add { task } => {
                     commands::gtd::today_add(&task, &config)?;
                 }
+                TodayAction::Done { item } => {
+                    commands::gtd::today_done(&item, &config)?;
+                }
+                TodayAction::Remove { item } => {
+                    commands::gtd::today_remove(&item, &config)?;
+                }
+                TodayAction::Edit { item, text } => {
+                    commands::gtd::today_edit(&item, text.as_deref(), &config)?;
+                }
             },
             GtdAction::Weekly => {
                 commands::gtd::weekly(&config)?;
@@ -61,7 +101,22 @@ fn main() -> Result<()> {
             GtdAction::Monthly => {
                 commands::gtd::monthly(&config)?;
             }
+            GtdAction::Process => {
+                commands::gtd::process(&config)?;
+            }
+            GtdAction::Tickler { move_to_inbox } => {
+                commands::gtd::tickler(move_to_inbox, &config)?;
+            }
         },
+        Commands::Next { contexts, time, energy, output } => {
+            commands::next::next(&contexts, time, energy, &output, &config)?;
+        }
+        Commands::Log { message, todo } => {
+            commands::log::log(message.as_deref(), todo.as_deref(), &config)?;
+        }
+        Commands::Agenda { days, output } => {
+            commands::agenda::agenda(days, &output, &config)?;
+        }
     }
 
     Ok(())
@@ -0,0 +1,293 @@
+/// A markdown document edited line by line, section by section.
+///
+/// Headings inside fenced code blocks are ignored. Lines that are not
+/// touched are written back exactly as they were.
+pub struct Document {
+    lines: Vec<String>,
+    trailing_newline: bool,
+}
+
+/// A `## ` section: its heading line and the first line after it (the next
+/// `#` / `##` heading or the end of the document)
+#[derive(Debug, Clone, Copy)]
+pub struct Section {
+    pub heading: usize,
+    pub end: usize,
+}
+
+/// A top-level list item together with its nested (indented) lines
+#[derive(Debug, Clone)]
+pub struct ListItem {
+    pub line: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+impl Document {
+    pub fn parse(text: &str) -> Self {
+        Self {
+            lines: text.lines().map(String::from).collect(),
+            trailing_newline: text.is_empty() || text.ends_with('\n'),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = self.lines.join("\n");
+        if self.trailing_newline && !self.lines.is_empty() {
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Lines `start..end`
+    pub fn lines(&self, start: usize, end: usize) -> &[String] {
+        &self.lines[start..end]
+    }
+
+    /// (line, level) of every heading outside fenced code blocks
+    fn headings(&self) -> Vec<(usize, usize)> {
+        let mut headings = Vec::new();
+        let mut in_fence = false;
+        for (i, line) in self.lines.iter().enumerate() {
+            if is_fence(line) {
+                in_fence = !in_fence;
+                continue;
+            }
+            if in_fence {
+                continue;
+            }
+            let level = line.chars().take_while(|c| *c == '#').count();
+            if level > 0 && line[level..].starts_with(' ') {
+                headings.push((i, level));
+            }
+        }
+        headings
+    }
+
+    /// First `## ` section whose heading text satisfies `matches`
+    pub fn find_section(&self, matches: impl Fn(&str) -> bool) -> Option<Section> {
+        let headings = self.headings();
+        let pos = headings
+            .iter()
+            .position(|&(i, level)| level == 2 && matches(self.lines[i][3..].trim()))?;
+        let heading = headings[pos].0;
+        let end = headings[pos + 1..]
+            .iter()
+            .find(|&&(_, level)| level <= 2)
+            .map(|&(i, _)| i)
+            .unwrap_or(self.lines.len());
+        Some(Section { heading, end })
+    }
+
+    /// The `## {heading}` section (case-insensitive)
+    pub fn section(&self, heading: &str) -> Option<Section> {
+        let heading = heading.trim().to_lowercase();
+        self.find_section(|h| h.to_lowercase() == heading)
+    }
+
+    /// Top-level list items of a section. Indented lines after an item
+    /// (nested lists, continuation text) belong to it, across blank lines.
+    pub fn items(&self, section: Section) -> Vec<ListItem> {
+        let mut items: Vec<ListItem> = Vec::new();
+        let mut in_fence = false;
+        let mut open = false;
+
+        for i in section.heading + 1..section.end {
+            let line = &self.lines[i];
+            if line.trim().is_empty() {
+                continue;
+            }
+            let indented = line.starts_with(' ') || line.starts_with('\t');
+            if !indented && is_fence(line) {
+                in_fence = !in_fence;
+                open = false;
+                continue;
+            }
+            if in_fence {
+                continue;
+            }
+
+            if indented {
+                if let Some(item) = items.last_mut().filter(|_| open) {
+                    item.end = i + 1;
+                }
+            } else if is_list_item(line) {
+                items.push(ListItem {
+                    line: i,
+                    end: i + 1,
+                    text: line.clone(),
+                });
+                open = true;
+            } else {
+                open = false;
+            }
+        }
+
+        items
+    }
+
+    /// Items of the `## {heading}` section (none when it is missing)
+    pub fn section_items(&self, heading: &str) -> Vec<ListItem> {
+        self.section(heading)
+            .map(|section| self.items(section))
+            .unwrap_or_default()
+    }
+
+    /// Add `entry` (one or more lines) after the last non-blank line of the
+    /// `## {heading}` section, which is created at the end when missing
+    pub fn append(&mut self, heading: &str, entry: &str) {
+        let entry_lines = entry.lines().map(String::from);
+
+        match self.section(heading) {
+            Some(section) => {
+                let last = (section.heading + 1..section.end)
+                    .rev()
+                    .find(|&i| !self.lines[i].trim().is_empty())
+                    .unwrap_or(section.heading);
+                self.lines.splice(last + 1..last + 1, entry_lines);
+            }
+            None => {
+                while self.lines.last().is_some_and(|l| l.trim().is_empty()) {
+                    self.lines.pop();
+                }
+                if !self.lines.is_empty() {
+                    self.lines.push(String::new());
+                }
+                self.lines.push(format!("## {}", heading));
+                self.lines.extend(entry_lines);
+                self.trailing_newline = true;
+            }
+        }
+    }
+
+    /// Remove an item with its nested lines; returns the removed lines
+    pub fn remove(&mut self, item: &ListItem) -> Vec<String> {
+        self.lines.drain(item.line..item.end).collect()
+    }
+
+    pub fn set_line(&mut self, index: usize, text: &str) {
+        self.lines[index] = text.to_string();
+    }
+
+    /// Put `text` in its own paragraph below the first `# ` heading, or at
+    /// the end when there is none
+    pub fn insert_below_title(&mut self, text: &str) {
+        let title = self
+            .headings()
+            .into_iter()
+            .find(|&(_, level)| level == 1)
+            .map(|(i, _)| i);
+
+        match title {
+            Some(i) => {
+                self.lines.splice(i + 1..i + 1, [String::new(), text.to_string()]);
+            }
+            None => {
+                if self.lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                    self.lines.push(String::new());
+                }
+                self.lines.push(text.to_string());
+                self.trailing_newline = true;
+            }
+        }
+    }
+}
+
+fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// `- `, `* `, `+ ` or `1. ` at the start of the line
+fn is_list_item(line: &str) -> bool {
+    if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
+        return true;
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    digits > 0 && line[digits..].starts_with(". ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = "\
+# 2026-10-18 Daily Report
+
+## TODO
+- [ ] Write report
+  - [ ] outline
+  - [x] data
+
+    notes under the item
+- [x] Call Bob
+
+## Done
+
+## Memo
+```
+## not a heading
+```
+";
+
+    #[test]
+    fn items_include_nested_lines() {
+        let doc = Document::parse(REPORT);
+        let items = doc.section_items("todo");
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].text, "- [ ] Write report");
+        assert_eq!(doc.lines(items[0].line + 1, items[0].end).len(), 4);
+        assert_eq!(items[1].text, "- [x] Call Bob");
+        assert!(doc.section("not a heading").is_none());
+    }
+
+    #[test]
+    fn append_goes_after_nested_children() {
+        let mut doc = Document::parse(REPORT);
+        doc.append("TODO", "- [ ] New task");
+        let expected = REPORT.replace("- [x] Call Bob\n", "- [x] Call Bob\n- [ ] New task\n");
+        assert_eq!(doc.render(), expected);
+
+        let mut doc = Document::parse("## TODO\n- [ ] a\n  - [ ] child\n\n## Done\n");
+        doc.append("TODO", "- [ ] b");
+        assert_eq!(doc.render(), "## TODO\n- [ ] a\n  - [ ] child\n- [ ] b\n\n## Done\n");
+    }
+
+    #[test]
+    fn append_skips_trailing_blank_lines() {
+        let mut doc = Document::parse("## Log\n- 09:00 start\n\n\n");
+        doc.append("log", "- 10:00 coffee");
+        assert_eq!(doc.render(), "## Log\n- 09:00 start\n- 10:00 coffee\n\n\n");
+
+        let mut doc = Document::parse("# Title\n\n## Done\n\n## Memo\n");
+        doc.append("Done", "- [x] a");
+        assert_eq!(doc.render(), "# Title\n\n## Done\n- [x] a\n\n## Memo\n");
+    }
+
+    #[test]
+    fn append_creates_missing_section() {
+        let mut doc = Document::parse("# Title\n\n## TODO\n- [ ] a\n\n");
+        doc.append("Canceled", "- [x] b");
+        assert_eq!(doc.render(), "# Title\n\n## TODO\n- [ ] a\n\n## Canceled\n- [x] b\n");
+
+        let mut doc = Document::parse("");
+        doc.append("Log", "- 09:00 start");
+        assert_eq!(doc.render(), "## Log\n- 09:00 start\n");
+    }
+
+    #[test]
+    fn remove_takes_nested_lines() {
+        let mut doc = Document::parse(REPORT);
+        let item = doc.section_items("TODO").remove(0);
+        let removed = doc.remove(&item);
+        assert_eq!(removed.len(), 5);
+        assert_eq!(doc.section_items("TODO")[0].text, "- [x] Call Bob");
+    }
+
+    #[test]
+    fn insert_below_title() {
+        let mut doc = Document::parse("---\ndate: x\n---\n\n# Title\n\n## TODO\n");
+        doc.insert_below_title("Previous: [[a]]");
+        assert_eq!(doc.render(), "---\ndate: x\n---\n\n# Title\n\nPrevious: [[a]]\n\n## TODO\n");
+    }
+}
@@ -0,0 +1,713 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use gray_matter::engine::{Engine, YAML};
+use gray_matter::Pod;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::markdown::Document;
+
+/// Heading of the log section in todo notes
+const LOG_HEADING: &str = "ログ (Log)";
+
+/// A markdown note with YAML frontmatter
+#[derive(Debug, Clone)]
+pub struct Note {
+    pub path: PathBuf,
+    pub frontmatter: Frontmatter,
+    pub body: String,
+}
+
+/// Frontmatter kept as ordered top-level entries.
+///
+/// Each entry holds its original lines, so writing the note back only
+/// rewrites the keys that were changed; unknown keys, comments and key order
+/// stay exactly as they were.
+#[derive(Debug, Clone, Default)]
+pub struct Frontmatter {
+    present: bool,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    /// Empty for comment or blank lines that precede the first key
+    key: String,
+    lines: Vec<String>,
+    value: Pod,
+}
+
+impl Note {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        Ok(Self::parse(path.to_path_buf(), &content))
+    }
+
+    pub fn parse(path: PathBuf, content: &str) -> Self {
+        let (frontmatter, body) = Frontmatter::split(content);
+        Self {
+            path,
+            frontmatter,
+            body,
+        }
+    }
+
+    /// First `# ` heading of the body, falling back to the `title:` field
+    pub fn title(&self) -> String {
+        for line in self.body.lines() {
+            if let Some(title) = line.strip_prefix("# ") {
+                return title.trim().to_string();
+            }
+        }
+        self.frontmatter.title().unwrap_or_default()
+    }
+
+    /// File name without the `.md` extension, as used in `[[links]]`
+    pub fn stem(&self) -> String {
+        self.path
+            .file_stem()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// (checked, total) task list items in the body
+    pub fn checklist(&self) -> (usize, usize) {
+        let boxes = checkboxes(&self.body);
+        let checked = boxes.iter().filter(|(_, checked)| *checked).count();
+        (checked, boxes.len())
+    }
+
+    /// Toggle the `n`th (1-based) checkbox in the body, leaving every other
+    /// line untouched. Returns the new state and the item text.
+    pub fn toggle_checkbox(&mut self, n: usize) -> Result<(bool, String)> {
+        let boxes = checkboxes(&self.body);
+        let Some(&(line_index, checked)) = n.checked_sub(1).and_then(|i| boxes.get(i)) else {
+            anyhow::bail!("Checkbox {} not found ({} in file)", n, boxes.len());
+        };
+
+        let mut lines: Vec<String> = self.body.split_inclusive('\n').map(String::from).collect();
+        let line = &mut lines[line_index];
+        let marker = line.find('[').unwrap_or(0);
+        let mark = if checked { " " } else { "x" };
+        line.replace_range(marker + 1..marker + 2, mark);
+        let text = line[marker + 3..].trim().to_string();
+        self.body = lines.concat();
+
+        Ok((!checked, text))
+    }
+
+    /// Uncheck every checkbox in the body
+    pub fn reset_checkboxes(&mut self) {
+        let boxes = checkboxes(&self.body);
+        let mut lines: Vec<String> = self.body.split_inclusive('\n').map(String::from).collect();
+        for (line_index, checked) in boxes {
+            if checked {
+                let line = &mut lines[line_index];
+                let marker = line.find('[').unwrap_or(0);
+                line.replace_range(marker + 1..marker + 2, " ");
+            }
+        }
+        self.body = lines.concat();
+    }
+
+    /// Append a line to the body's `## ログ (Log)` section, as in the todo
+    /// template. The section is added when the note has none.
+    pub fn append_log(&mut self, entry: &str) {
+        let mut doc = Document::parse(&self.body);
+        doc.append(LOG_HEADING, entry);
+        self.body = doc.render();
+    }
+
+    pub fn render(&self) -> String {
+        let mut content = self.frontmatter.render();
+        content.push_str(&self.body);
+        content
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, self.render())
+            .with_context(|| format!("Failed to write file: {:?}", self.path))
+    }
+}
+
+impl Frontmatter {
+    /// Split content into frontmatter and the untouched body that follows
+    /// the closing `---` line.
+    fn split(content: &str) -> (Self, String) {
+        let mut lines = content.split_inclusive('\n');
+
+        let mut consumed = match lines.next() {
+            Some(first) if first.trim_end() == "---" => first.len(),
+            _ => return (Self::default(), content.to_string()),
+        };
+
+        let mut matter_lines: Vec<&str> = Vec::new();
+        let mut closed = false;
+
+        for line in lines {
+            consumed += line.len();
+            if line.trim_end() == "---" {
+                closed = true;
+                break;
+            }
+            matter_lines.push(line.trim_end_matches(['\n', '\r']));
+        }
+
+        if !closed {
+            return (Self::default(), content.to_string());
+        }
+
+        let frontmatter = Self {
+            present: true,
+            entries: group_entries(&matter_lines),
+        };
+
+        (frontmatter, content[consumed..].to_string())
+    }
+
+    pub fn is_present(&self) -> bool {
+        self.present
+    }
+
+    fn render(&self) -> String {
+        if !self.present {
+            return String::new();
+        }
+
+        let mut out = String::from("---\n");
+        for entry in &self.entries {
+            for line in &entry.lines {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+        out.push_str("---\n");
+        out
+    }
+
+    /// All keys with their parsed values, in frontmatter order
+    pub fn to_json(&self) -> Map<String, Value> {
+        let mut map = Map::new();
+        for entry in self.entries.iter().filter(|e| !e.key.is_empty()) {
+            map.insert(entry.key.clone(), entry.value.clone().into());
+        }
+        map
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entry(key).is_some()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Pod> {
+        self.entry(key).map(|e| &e.value)
+    }
+
+    /// Scalar value as a string; `None` for missing, null or empty values
+    pub fn get_str(&self, key: &str) -> Option<String> {
+        let value = match self.get(key)? {
+            Pod::String(s) => s.trim().to_string(),
+            Pod::Integer(i) => i.to_string(),
+            Pod::Float(f) => f.to_string(),
+            Pod::Boolean(b) => b.to_string(),
+            _ => return None,
+        };
+        if value.is_empty() {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// List value; a scalar is treated as a comma separated list
+    pub fn get_list(&self, key: &str) -> Vec<String> {
+        match self.get(key) {
+            Some(Pod::Array(items)) => items
+                .iter()
+                .filter_map(|item| match item {
+                    Pod::String(s) => Some(s.trim().to_string()),
+                    Pod::Integer(i) => Some(i.to_string()),
+                    Pod::Float(f) => Some(f.to_string()),
+                    Pod::Boolean(b) => Some(b.to_string()),
+                    _ => None,
+                })
+                .filter(|s| !s.is_empty())
+                .collect(),
+            _ => self
+                .get_str(key)
+                .map(|s| {
+                    s.split(',')
+                        .map(|part| part.trim().to_string())
+                        .filter(|part| !part.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Date value parsed with `date_format`, falling back to ISO `%Y-%m-%d`
+    pub fn get_date(&self, key: &str, date_format: &str) -> Option<NaiveDate> {
+        let value = self.get_str(key)?;
+        NaiveDate::parse_from_str(&value, date_format)
+            .or_else(|_| NaiveDate::parse_from_str(&value, "%Y-%m-%d"))
+            .ok()
+    }
+
+    pub fn title(&self) -> Option<String> {
+        self.get_str("title")
+    }
+
+    pub fn status(&self) -> Option<String> {
+        self.get_str("status")
+    }
+
+    /// Creation date string (`created:` or `date:`)
+    pub fn created(&self) -> Option<String> {
+        self.get_str("created").or_else(|| self.get_str("date"))
+    }
+
+    pub fn created_date(&self, date_format: &str) -> Option<NaiveDate> {
+        self.get_date("created", date_format)
+            .or_else(|| self.get_date("date", date_format))
+    }
+
+    /// Due date string (`due_date:` or `due:`)
+    pub fn due(&self) -> Option<String> {
+        self.get_str("due_date").or_else(|| self.get_str("due"))
+    }
+
+    pub fn due_date(&self, date_format: &str) -> Option<NaiveDate> {
+        self.get_date("due_date", date_format)
+            .or_else(|| self.get_date("due", date_format))
+    }
+
+    pub fn completed(&self) -> Option<String> {
+        self.get_str("completed")
+    }
+
+    pub fn review_date(&self, date_format: &str) -> Option<NaiveDate> {
+        self.get_date("review_date", date_format)
+    }
+
+    pub fn project(&self) -> Option<String> {
+        self.get_str("project")
+    }
+
+    pub fn tags(&self) -> Vec<String> {
+        self.get_list("tags")
+    }
+
+    /// Contexts such as `@office`; accepts a single value or a list
+    pub fn contexts(&self) -> Vec<String> {
+        self.get_list("context")
+    }
+
+    pub fn estimate(&self) -> Option<String> {
+        self.get_str("estimate")
+    }
+
+    /// Estimate in minutes; accepts `30`, `30m`, `1h`, `1h30m` and `1.5h`
+    pub fn estimate_minutes(&self) -> Option<u32> {
+        parse_duration_minutes(&self.estimate()?)
+    }
+
+    /// Set a scalar value, keeping the key's position and quote style.
+    /// New keys are appended at the end of the frontmatter.
+    pub fn set(&mut self, key: &str, value: &str) {
+        let quoted = self
+            .entry(key)
+            .and_then(|e| e.lines.first())
+            .and_then(|line| line.split_once(':'))
+            .map(|(_, v)| v.trim().starts_with('"'))
+            .unwrap_or(false);
+
+        let rendered = render_scalar(value, quoted);
+        let line = if rendered.is_empty() {
+            format!("{}:", key)
+        } else {
+            format!("{}: {}", key, rendered)
+        };
+        let pod = if value.is_empty() {
+            Pod::Null
+        } else {
+            Pod::String(value.to_string())
+        };
+
+        self.put(key, vec![line], pod);
+    }
+
+    /// Set the due date on `due_date:`, or on `due:` when only that key exists
+    pub fn set_due(&mut self, value: &str) {
+        let key = if self.contains_key("due") && !self.contains_key("due_date") {
+            "due"
+        } else {
+            "due_date"
+        };
+        self.set(key, value);
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.entries.retain(|e| e.key != key);
+    }
+
+    fn entry(&self, key: &str) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.key == key)
+    }
+
+    fn put(&mut self, key: &str, lines: Vec<String>, value: Pod) {
+        self.present = true;
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.key == key) {
+            entry.lines = lines;
+            entry.value = value;
+        } else {
+            self.entries.push(Entry {
+                key: key.to_string(),
+                lines,
+                value,
+            });
+        }
+    }
+}
+
+/// Group frontmatter lines into top-level entries. Indented lines and list
+/// items belong to the key above them.
+fn group_entries(lines: &[&str]) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = Vec::new();
+
+    for line in lines {
+        let is_continuation = line.starts_with(' ')
+            || line.starts_with('\t')
+            || line.starts_with("- ")
+            || line.trim().is_empty()
+            || line.starts_with('#');
+
+        let key = if is_continuation {
+            None
+        } else {
+            line.split_once(':')
+                .map(|(k, _)| k.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+        };
+
+        match key {
+            Some(key) => entries.push(Entry {
+                key,
+                lines: vec![line.to_string()],
+                value: Pod::Null,
+            }),
+            None => match entries.last_mut() {
+                Some(entry) => entry.lines.push(line.to_string()),
+                None => entries.push(Entry {
+                    key: String::new(),
+                    lines: vec![line.to_string()],
+                    value: Pod::Null,
+                }),
+            },
+        }
+    }
+
+    for entry in &mut entries {
+        if !entry.key.is_empty() {
+            entry.value = parse_entry_value(entry);
+        }
+    }
+
+    entries
+}
+
+/// Parse one entry as YAML. Entries are parsed individually so a single
+/// invalid value (e.g. an unquoted `@office` or a title with `: `) does not
+/// hide the rest; such values fall back to their raw text.
+fn parse_entry_value(entry: &Entry) -> Pod {
+    let yaml = entry.lines.join("\n");
+    if let Pod::Hash(mut hash) = YAML::parse(&yaml) {
+        if let Some(value) = hash.remove(&entry.key) {
+            return value;
+        }
+    }
+
+    let raw = entry
+        .lines
+        .first()
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, v)| v.trim())
+        .unwrap_or_default();
+    let raw = raw
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(raw);
+
+    if raw.is_empty() {
+        Pod::Null
+    } else {
+        Pod::String(raw.to_string())
+    }
+}
+
+/// Render a scalar for YAML, quoting it when plain style would change its meaning
+fn render_scalar(value: &str, force_quote: bool) -> String {
+    let needs_quote = force_quote
+        || value.contains(": ")
+        || value.contains(" #")
+        || value.ends_with(':')
+        || value.starts_with(|c: char| "[]{}&*!|>'\"%@`#,?-".contains(c))
+        || value.starts_with(' ')
+        || value.ends_with(' ');
+
+    if needs_quote {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// (line index, checked) of every `- [ ]` / `- [x]` item (also `*` and `+`
+/// bullets), skipping fenced code blocks and empty placeholders like the
+/// template's bare `- [ ]`
+fn checkboxes(body: &str) -> Vec<(usize, bool)> {
+    let mut boxes = Vec::new();
+    let mut in_code = false;
+
+    for (i, line) in body.split_inclusive('\n').enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+
+        let Some(rest) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .or_else(|| trimmed.strip_prefix("+ "))
+        else {
+            continue;
+        };
+        let checked = if rest.starts_with("[ ]") {
+            false
+        } else if rest.starts_with("[x]") || rest.starts_with("[X]") {
+            true
+        } else {
+            continue;
+        };
+        if !rest[3..].trim().is_empty() {
+            boxes.push((i, checked));
+        }
+    }
+
+    boxes
+}
+
+/// Parse durations like `30`, `30m`, `45min`, `2h`, `1h30m` or `1.5h` into minutes
+pub fn parse_duration_minutes(s: &str) -> Option<u32> {
+    let s = s.trim().to_lowercase();
+    if s.is_empty() {
+        return None;
+    }
+    if let Ok(minutes) = s.parse::<u32>() {
+        return Some(minutes);
+    }
+
+    let mut total = 0.0;
+    let mut number = String::new();
+    let mut chars = s.chars().peekable();
+    let mut matched = false;
+
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+        if c.is_whitespace() {
+            continue;
+        }
+
+        let value: f64 = number.parse().ok()?;
+        number.clear();
+        match c {
+            'h' => {
+                total += value * 60.0;
+                // Accept "hr", "hrs", "hour", "hours"
+                while chars.peek().map(|c| c.is_alphabetic()).unwrap_or(false) {
+                    chars.next();
+                }
+            }
+            'm' => {
+                total += value;
+                // Accept "min", "mins", "minutes"
+                while chars.peek().map(|c| c.is_alphabetic()).unwrap_or(false) {
+                    chars.next();
+                }
+            }
+            _ => return None,
+        }
+        matched = true;
+    }
+
+    // A trailing bare number after hours counts as minutes ("1h30")
+    if !number.is_empty() {
+        if !matched {
+            return None;
+        }
+        total += number.parse::<f64>().ok()?;
+    }
+
+    if matched {
+        Some(total.round() as u32)
+    } else {
+        None
+    }
+}
+
+/// Collect notes from `dir`, descending into subdirectories when `recursive`
+pub fn collect_notes(dir: &Path, recursive: bool, notes: &mut Vec<Note>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_dir() {
+            if recursive {
+                collect_notes(&path, recursive, notes)?;
+            }
+        } else if path.is_file() && path.extension().map(|e| e == "md").unwrap_or(false) {
+            if let Ok(content) = fs::read_to_string(&path) {
+                notes.push(Note::parse(path, &content));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "\
+---
+# kept comment
+title: \"Review: Q3 plan\"
+status: next
+tags: [work, 会議]
+context:
+  - \"@office\"
+  - phone
+description: |
+  first line
+  second line
+url: https://example.com/a:b
+due_date: 2026-10-20
+---
+# Review
+
+- [ ] one
+";
+
+    fn parse(content: &str) -> Note {
+        Note::parse(PathBuf::from("note.md"), content)
+    }
+
+    #[test]
+    fn parses_values() {
+        let note = parse(CONTENT);
+        let fm = &note.frontmatter;
+        assert_eq!(fm.title().as_deref(), Some("Review: Q3 plan"));
+        assert_eq!(fm.tags(), vec!["work", "会議"]);
+        assert_eq!(fm.contexts(), vec!["@office", "phone"]);
+        assert_eq!(fm.get_str("description").as_deref(), Some("first line\nsecond line"));
+        assert_eq!(fm.get_str("url").as_deref(), Some("https://example.com/a:b"));
+        assert_eq!(fm.due_date("%Y-%m-%d"), NaiveDate::from_ymd_opt(2026, 10, 20));
+        assert_eq!(note.body, "# Review\n\n- [ ] one\n");
+    }
+
+    #[test]
+    fn round_trips_unchanged() {
+        assert_eq!(parse(CONTENT).render(), CONTENT);
+    }
+
+    #[test]
+    fn set_keeps_order_and_quotes() {
+        let mut note = parse(CONTENT);
+        note.frontmatter.set("title", "Review Q4");
+        note.frontmatter.set("status", "done");
+        note.frontmatter.set("completed", "2026-10-18");
+        note.frontmatter.remove("due_date");
+
+        let expected = CONTENT
+            .replace("title: \"Review: Q3 plan\"", "title: \"Review Q4\"")
+            .replace("status: next", "status: done")
+            .replace("due_date: 2026-10-20", "completed: 2026-10-18");
+        assert_eq!(note.render(), expected);
+    }
+
+    #[test]
+    fn set_quotes_values_that_need_it() {
+        let mut note = parse("---\nstatus: inbox\n---\n");
+        note.frontmatter.set("title", "Ask: budget");
+        note.frontmatter.set("context", "@phone");
+        note.frontmatter.set("project", "");
+
+        let rendered = note.render();
+        assert_eq!(
+            rendered,
+            "---\nstatus: inbox\ntitle: \"Ask: budget\"\ncontext: \"@phone\"\nproject:\n---\n"
+        );
+        let fm = parse(&rendered).frontmatter;
+        assert_eq!(fm.title().as_deref(), Some("Ask: budget"));
+        assert_eq!(fm.contexts(), vec!["@phone"]);
+        assert_eq!(fm.project(), None);
+    }
+
+    #[test]
+    fn invalid_value_does_not_hide_other_keys() {
+        let fm = parse("---\ntitle: Plan: draft\ncontext: @office\nstatus: next\n---\n").frontmatter;
+        assert_eq!(fm.title().as_deref(), Some("Plan: draft"));
+        assert_eq!(fm.contexts(), vec!["@office"]);
+        assert_eq!(fm.status().as_deref(), Some("next"));
+    }
+
+    #[test]
+    fn append_log_targets_the_log_section_only() {
+        let mut note = parse("# Task\n\n## Blog ideas\n- a\n\n## カタログ\n- b\n\n## ログ (Log)\n- old\n\n## Notes\n");
+        note.append_log("- new");
+        assert_eq!(
+            note.body,
+            "# Task\n\n## Blog ideas\n- a\n\n## カタログ\n- b\n\n## ログ (Log)\n- old\n- new\n\n## Notes\n"
+        );
+
+        let mut note = parse("# Task\n\n## Blog ideas\n- a\n");
+        note.append_log("- new");
+        assert_eq!(note.body, "# Task\n\n## Blog ideas\n- a\n\n## ログ (Log)\n- new\n");
+    }
+
+    #[test]
+    fn checklist_skips_empty_placeholders() {
+        let template = include_str!("../templates/todo.md");
+        let mut note = parse(template);
+        assert_eq!(note.checklist(), (0, 3));
+
+        let (checked, text) = note.toggle_checkbox(1).unwrap();
+        assert!(checked);
+        assert_eq!(text, "これは実行可能か？");
+        assert!(note.body.contains("- [x] これは実行可能か？"));
+        assert_eq!(note.checklist(), (1, 3));
+        assert!(note.toggle_checkbox(4).is_err());
+
+        let note = parse("- [ ] a\n- [x]\n- [X]   \n* [x] b\n");
+        assert_eq!(note.checklist(), (1, 2));
+    }
+
+    #[test]
+    fn without_frontmatter_body_is_untouched() {
+        let content = "# Title\n---\ntext\n";
+        let note = parse(content);
+        assert!(!note.frontmatter.is_present());
+        assert_eq!(note.render(), content);
+    }
+}
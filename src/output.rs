@@ -0,0 +1,173 @@
+use anyhow::Result;
+use serde_json::{Map, Value};
+use std::io::{self, Write};
+use std::path::Path;
+use crate::cli::OutputFormat;
+use crate::note::Frontmatter;
+
+/// One row of a listing in machine-readable form
+#[derive(Default)]
+pub struct Record {
+    fields: Vec<(String, Value)>,
+    frontmatter: Option<Map<String, Value>>,
+}
+
+impl Record {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn field(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.fields.push((name.to_string(), value.into()));
+        self
+    }
+
+    /// Absolute path plus the path relative to the notes root
+    pub fn paths(self, path: &Path, root_dir: &Path) -> Self {
+        let relative = path
+            .strip_prefix(root_dir)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| path.display().to_string());
+        self.field("path", path.display().to_string())
+            .field("relative_path", relative)
+    }
+
+    pub fn frontmatter(mut self, frontmatter: &Frontmatter) -> Self {
+        self.frontmatter = Some(frontmatter.to_json());
+        self
+    }
+
+    fn path(&self) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == "path")
+            .and_then(|(_, v)| v.as_str())
+    }
+
+    fn to_json(&self) -> Value {
+        let mut map = Map::new();
+        for (name, value) in &self.fields {
+            map.insert(name.clone(), value.clone());
+        }
+        if let Some(ref frontmatter) = self.frontmatter {
+            map.insert("frontmatter".to_string(), Value::Object(frontmatter.clone()));
+        }
+        Value::Object(map)
+    }
+}
+
+/// Print records in a non-text format. Text output is left to each command.
+pub fn print_records(format: OutputFormat, records: &[Record]) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+
+    match format {
+        OutputFormat::Text => {}
+        OutputFormat::Json => {
+            let values: Vec<Value> = records.iter().map(|r| r.to_json()).collect();
+            serde_json::to_writer_pretty(&mut stdout, &values)?;
+            writeln!(stdout)?;
+        }
+        OutputFormat::Paths => {
+            for record in records {
+                if let Some(path) = record.path() {
+                    writeln!(stdout, "{}", path)?;
+                }
+            }
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let separator = if format == OutputFormat::Csv { "," } else { "\t" };
+            let columns = table_columns(records);
+
+            let header: Vec<String> = columns
+                .iter()
+                .map(|c| escape_cell(&c.header(), format))
+                .collect();
+            writeln!(stdout, "{}", header.join(separator))?;
+
+            for record in records {
+                let row: Vec<String> = columns
+                    .iter()
+                    .map(|c| escape_cell(&c.value(record), format))
+                    .collect();
+                writeln!(stdout, "{}", row.join(separator))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+enum Column {
+    Field(String),
+    Frontmatter(String),
+}
+
+impl Column {
+    fn header(&self) -> String {
+        match self {
+            Column::Field(name) => name.clone(),
+            Column::Frontmatter(key) => format!("frontmatter.{}", key),
+        }
+    }
+
+    fn value(&self, record: &Record) -> String {
+        let value = match self {
+            Column::Field(name) => record
+                .fields
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v),
+            Column::Frontmatter(key) => record.frontmatter.as_ref().and_then(|fm| fm.get(key)),
+        };
+        value.map(cell_text).unwrap_or_default()
+    }
+}
+
+/// Record fields followed by every frontmatter key, in first-seen order
+fn table_columns(records: &[Record]) -> Vec<Column> {
+    let mut fields: Vec<String> = Vec::new();
+    let mut keys: Vec<String> = Vec::new();
+
+    for record in records {
+        for (name, _) in &record.fields {
+            if !fields.contains(name) {
+                fields.push(name.clone());
+            }
+        }
+        if let Some(ref frontmatter) = record.frontmatter {
+            for key in frontmatter.keys() {
+                if !keys.contains(key) {
+                    keys.push(key.clone());
+                }
+            }
+        }
+    }
+
+    fields
+        .into_iter()
+        .map(Column::Field)
+        .chain(keys.into_iter().map(Column::Frontmatter))
+        .collect()
+}
+
+/// Flatten a JSON value for a table cell; lists are joined with `;`
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(cell_text).collect::<Vec<_>>().join(";"),
+        other => other.to_string(),
+    }
+}
+
+fn escape_cell(cell: &str, format: OutputFormat) -> String {
+    if format == OutputFormat::Tsv {
+        return cell.replace(['\t', '\n', '\r'], " ");
+    }
+
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
@@ -0,0 +1,559 @@
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use crate::config::Config;
+use crate::dates::parse_date;
+use crate::deps::is_closed_status;
+use crate::note::{parse_duration_minutes, Note};
+
+/// Filter expression over note frontmatter, e.g.
+/// `status:next AND (due<=+3d OR tag:urgent) NOT context:@home`.
+///
+/// Terms are `field<op>value` with `:`/`=` (equals, or contains for lists),
+/// `!=`, `<`, `<=`, `>` and `>=`. Bare words match the title and body.
+/// `<`, `<=`, `>` and `>=` only apply to dates and estimates; other fields
+/// only take `:`/`=`/`!=`.
+/// Adjacent terms are combined with AND; `OR`, `NOT`/`!` and parentheses
+/// are supported.
+#[derive(Debug, Clone)]
+pub enum Query {
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Not(Box<Query>),
+    Term(Term),
+}
+
+#[derive(Debug, Clone)]
+pub struct Term {
+    field: Option<String>,
+    op: Op,
+    value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Values a query needs besides the note itself
+pub struct QueryContext {
+    pub today: NaiveDate,
+    pub date_format: String,
+}
+
+impl QueryContext {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            today: Local::now().date_naive(),
+            date_format: config.general.date_format.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Word(String),
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            anyhow::bail!("Empty filter");
+        }
+
+        let mut parser = Parser { tokens, pos: 0 };
+        let query = parser.parse_or()?;
+        if parser.pos < parser.tokens.len() {
+            anyhow::bail!("unexpected ')'");
+        }
+        Ok(query)
+    }
+
+    /// Parse several filters (e.g. repeated `-f` options) combined with AND
+    pub fn parse_all(filters: &[String]) -> Result<Option<Self>> {
+        let mut combined: Option<Query> = None;
+        for filter in filters {
+            let query = Self::parse(filter)
+                .map_err(|e| anyhow::anyhow!("Invalid filter '{}': {}", filter, e))?;
+            combined = Some(match combined {
+                Some(prev) => Query::And(Box::new(prev), Box::new(query)),
+                None => query,
+            });
+        }
+        Ok(combined)
+    }
+
+    pub fn matches(&self, note: &Note, ctx: &QueryContext) -> bool {
+        match self {
+            Query::And(a, b) => a.matches(note, ctx) && b.matches(note, ctx),
+            Query::Or(a, b) => a.matches(note, ctx) || b.matches(note, ctx),
+            Query::Not(q) => !q.matches(note, ctx),
+            Query::Term(term) => term.matches(note, ctx),
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn parse_or(&mut self) -> Result<Query> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            let right = self.parse_and()?;
+            left = Query::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Query> {
+        let mut left = self.parse_unary()?;
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.pos += 1;
+                }
+                // Juxtaposition means AND
+                Some(Token::Word(_)) | Some(Token::Not) | Some(Token::LParen) => {}
+                _ => break,
+            }
+            let right = self.parse_unary()?;
+            left = Query::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Query> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            let inner = self.parse_unary()?;
+            return Ok(Query::Not(Box::new(inner)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Query> {
+        match self.tokens.get(self.pos).cloned() {
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let inner = self.parse_or()?;
+                if self.peek() != Some(&Token::RParen) {
+                    anyhow::bail!("missing ')'");
+                }
+                self.pos += 1;
+                Ok(inner)
+            }
+            Some(Token::Word(word)) => {
+                self.pos += 1;
+                Ok(Query::Term(parse_term(&word)?))
+            }
+            Some(token) => anyhow::bail!("unexpected {:?}", token),
+            None => anyhow::bail!("unexpected end of filter"),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    let flush = |current: &mut String, tokens: &mut Vec<Token>| {
+        if current.is_empty() {
+            return;
+        }
+        let token = match current.as_str() {
+            "AND" | "and" | "&&" => Token::And,
+            "OR" | "or" | "||" => Token::Or,
+            "NOT" | "not" => Token::Not,
+            _ => Token::Word(current.clone()),
+        };
+        tokens.push(token);
+        current.clear();
+    };
+
+    for c in input.chars() {
+        if in_quotes {
+            if c == '"' {
+                in_quotes = false;
+            }
+            current.push(c);
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_quotes = true;
+                current.push(c);
+            }
+            '(' => {
+                flush(&mut current, &mut tokens);
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                flush(&mut current, &mut tokens);
+                tokens.push(Token::RParen);
+            }
+            '!' if current.is_empty() => {
+                tokens.push(Token::Not);
+            }
+            c if c.is_whitespace() => flush(&mut current, &mut tokens),
+            _ => current.push(c),
+        }
+    }
+
+    if in_quotes {
+        anyhow::bail!("unterminated quote");
+    }
+    flush(&mut current, &mut tokens);
+
+    Ok(tokens)
+}
+
+/// Date and number fields, the only ones ordering operators make sense on
+const ORDERED_FIELDS: [&str; 9] = [
+    "due", "due_date", "defer", "review", "review_date", "created", "date", "completed", "estimate",
+];
+
+fn parse_term(word: &str) -> Result<Term> {
+    // Operators in the order they must be tried (two-char first)
+    let operators = [
+        ("!=", Op::Ne),
+        ("<=", Op::Le),
+        (">=", Op::Ge),
+        ("<", Op::Lt),
+        (">", Op::Gt),
+        ("=", Op::Eq),
+        (":", Op::Eq),
+    ];
+
+    let split = operators
+        .iter()
+        .filter_map(|(symbol, op)| word.find(symbol).map(|idx| (idx, *symbol, *op)))
+        .min_by_key(|(idx, symbol, _)| (*idx, std::cmp::Reverse(symbol.len())));
+
+    if let Some((idx, symbol, op)) = split {
+        let field = &word[..idx];
+        let is_field = !field.is_empty()
+            && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if is_field {
+            let field = field.to_lowercase();
+            if !ORDERED_FIELDS.contains(&field.as_str()) && !matches!(op, Op::Eq | Op::Ne) {
+                anyhow::bail!("'{}' only supports ':', '=' and '!=' (in '{}')", field, word);
+            }
+            return Ok(Term {
+                field: Some(field),
+                op,
+                value: unquote(&word[idx + symbol.len()..]),
+            });
+        }
+    }
+
+    Ok(Term {
+        field: None,
+        op: Op::Eq,
+        value: unquote(word),
+    })
+}
+
+fn unquote(s: &str) -> String {
+    s.strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(s)
+        .to_string()
+}
+
+impl Term {
+    fn matches(&self, note: &Note, ctx: &QueryContext) -> bool {
+        let field = match self.field.as_deref() {
+            None | Some("text") => return self.matches_text(note),
+            Some(field) => field,
+        };
+
+        match field {
+            "title" => self.compare_contains(&note.title()),
+            "tag" | "tags" => self.matches_list(&note.frontmatter.tags(), false),
+            "context" | "contexts" => self.matches_list(&note.frontmatter.contexts(), true),
+            "due" | "due_date" => self.matches_date(note.frontmatter.due_date(&ctx.date_format), note, ctx),
+            "review" | "review_date" | "defer" => self.matches_date(note.frontmatter.review_date(&ctx.date_format), note, ctx),
+            "created" | "date" => {
+                let created = note.frontmatter.get_date("created", &ctx.date_format)
+                    .or_else(|| note.frontmatter.get_date("date", &ctx.date_format));
+                self.matches_date(created, note, ctx)
+            }
+            "completed" => self.matches_date(note.frontmatter.get_date("completed", &ctx.date_format), note, ctx),
+            "estimate" => self.matches_minutes(note.frontmatter.estimate_minutes()),
+            "project" => {
+                let project = note.frontmatter.project();
+                // Accept both "name" and the "00831_name" directory form
+                let stripped = project.as_deref().map(strip_project_number);
+                self.matches_scalar(project.as_deref())
+                    || (self.op == Op::Eq && self.matches_scalar(stripped))
+            }
+            _ => self.matches_scalar(note.frontmatter.get_str(field).as_deref()),
+        }
+    }
+
+    fn matches_text(&self, note: &Note) -> bool {
+        let needle = self.value.to_lowercase();
+        let found = note.title().to_lowercase().contains(&needle)
+            || note.body.to_lowercase().contains(&needle);
+        if self.op == Op::Ne {
+            !found
+        } else {
+            found
+        }
+    }
+
+    fn compare_contains(&self, haystack: &str) -> bool {
+        let found = haystack.to_lowercase().contains(&self.value.to_lowercase());
+        match self.op {
+            Op::Ne => !found,
+            _ => found,
+        }
+    }
+
+    /// `none` matches a missing field; otherwise compare case-insensitively,
+    /// numerically when both sides are numbers.
+    fn matches_scalar(&self, actual: Option<&str>) -> bool {
+        if self.value.eq_ignore_ascii_case("none") {
+            return match self.op {
+                Op::Ne => actual.is_some(),
+                _ => actual.is_none(),
+            };
+        }
+
+        let actual = match actual {
+            Some(actual) => actual,
+            None => return self.op == Op::Ne,
+        };
+
+        let ordering = match (actual.parse::<f64>(), self.value.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.partial_cmp(&b),
+            _ => Some(actual.to_lowercase().cmp(&self.value.to_lowercase())),
+        };
+
+        ordering.map(|o| self.op.accepts(o)).unwrap_or(false)
+    }
+
+    /// Membership test; `:`/`=` means "contains", `!=` means "does not contain"
+    fn matches_list(&self, items: &[String], is_context: bool) -> bool {
+        if self.value.eq_ignore_ascii_case("none") {
+            return match self.op {
+                Op::Ne => !items.is_empty(),
+                _ => items.is_empty(),
+            };
+        }
+
+        let normalize = |s: &str| {
+            let s = s.to_lowercase();
+            if is_context {
+                s.trim_start_matches('@').to_string()
+            } else {
+                s.trim_start_matches('#').to_string()
+            }
+        };
+        let wanted = normalize(&self.value);
+        let found = items.iter().any(|item| normalize(item) == wanted);
+
+        match self.op {
+            Op::Ne => !found,
+            _ => found,
+        }
+    }
+
+    fn matches_date(&self, actual: Option<NaiveDate>, note: &Note, ctx: &QueryContext) -> bool {
+        let value = self.value.to_lowercase();
+
+        if value == "none" {
+            return match self.op {
+                Op::Ne => actual.is_some(),
+                _ => actual.is_none(),
+            };
+        }
+
+        if value == "overdue" {
+            let is_open = note
+                .frontmatter
+                .status()
+                .map(|s| !is_closed_status(&s))
+                .unwrap_or(false);
+            let overdue = is_open && actual.map(|d| d < ctx.today).unwrap_or(false);
+            return match self.op {
+                Op::Ne => !overdue,
+                _ => overdue,
+            };
+        }
+
+        let target = match parse_date(&value, ctx.today, &ctx.date_format) {
+            Some(target) => target,
+            None => return false,
+        };
+
+        match actual {
+            Some(actual) => self.op.accepts(actual.cmp(&target)),
+            None => self.op == Op::Ne,
+        }
+    }
+
+    fn matches_minutes(&self, actual: Option<u32>) -> bool {
+        if self.value.eq_ignore_ascii_case("none") {
+            return match self.op {
+                Op::Ne => actual.is_some(),
+                _ => actual.is_none(),
+            };
+        }
+
+        match (actual, parse_duration_minutes(&self.value)) {
+            (Some(actual), Some(target)) => self.op.accepts(actual.cmp(&target)),
+            (None, Some(_)) => self.op == Op::Ne,
+            _ => false,
+        }
+    }
+}
+
+impl Op {
+    fn accepts(self, ordering: std::cmp::Ordering) -> bool {
+        use std::cmp::Ordering::*;
+        match self {
+            Op::Eq => ordering == Equal,
+            Op::Ne => ordering != Equal,
+            Op::Lt => ordering == Less,
+            Op::Le => ordering != Greater,
+            Op::Gt => ordering == Greater,
+            Op::Ge => ordering != Less,
+        }
+    }
+}
+
+fn strip_project_number(project: &str) -> &str {
+    match project.split_once('_') {
+        Some((number, name)) if number.chars().all(|c| c.is_ascii_digit()) => name,
+        _ => project,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn ctx() -> QueryContext {
+        QueryContext {
+            today: NaiveDate::from_ymd_opt(2026, 10, 18).unwrap(),
+            date_format: "%Y-%m-%d".to_string(),
+        }
+    }
+
+    fn note(frontmatter: &str) -> Note {
+        let content = format!("---\n{}\n---\n# Task\n\nbody text\n", frontmatter);
+        Note::parse(PathBuf::from("task.md"), &content)
+    }
+
+    fn matches(filter: &str, frontmatter: &str) -> bool {
+        Query::parse(filter).unwrap().matches(&note(frontmatter), &ctx())
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        // a OR (b AND c)
+        let filter = "status:next OR tag:urgent AND context:@office";
+        assert!(matches(filter, "status: next"));
+        assert!(matches(filter, "status: inbox\ntags: [urgent]\ncontext: \"@office\""));
+        assert!(!matches(filter, "status: inbox\ntags: [urgent]"));
+    }
+
+    #[test]
+    fn not_binds_tightest() {
+        // (NOT a) AND b
+        let filter = "NOT status:done tag:urgent";
+        assert!(matches(filter, "status: next\ntags: [urgent]"));
+        assert!(!matches(filter, "status: done\ntags: [urgent]"));
+        assert!(!matches(filter, "status: next"));
+        assert!(matches("!status:done", "status: next"));
+    }
+
+    #[test]
+    fn parentheses_group() {
+        let filter = "(status:next OR tag:urgent) AND context:@office";
+        assert!(!matches(filter, "status: next"));
+        assert!(matches(filter, "status: next\ncontext: \"@office\""));
+        assert!(matches("NOT (status:done OR status:canceled)", "status: waiting"));
+        assert!(!matches("NOT (status:done OR status:canceled)", "status: canceled"));
+    }
+
+    #[test]
+    fn relative_due_dates() {
+        assert!(matches("due<=+3d", "due_date: 2026-10-21"));
+        assert!(!matches("due<=+3d", "due_date: 2026-10-22"));
+        assert!(matches("due<=+3d", "due_date: 2026-10-01"));
+        assert!(!matches("due<=+3d", "status: next"));
+        assert!(matches("due:none", "status: next"));
+        assert!(matches("due:today", "due: 2026-10-18"));
+        assert!(matches("due:overdue", "status: next\ndue_date: 2026-10-17"));
+        assert!(!matches("due:overdue", "status: done\ndue_date: 2026-10-17"));
+    }
+
+    #[test]
+    fn defer_is_the_review_date() {
+        assert!(matches("defer>today", "review_date: 2026-10-20"));
+        assert!(matches("review<=+7d", "review_date: 2026-10-20"));
+        assert!(!matches("defer>today", "review_date: 2026-10-18"));
+    }
+
+    #[test]
+    fn estimates_compare_in_minutes() {
+        assert!(matches("estimate<30m", "estimate: 15m"));
+        assert!(!matches("estimate<30m", "estimate: 30"));
+        assert!(matches("estimate<=0.5h", "estimate: 30m"));
+        assert!(matches("estimate>1h", "estimate: 1h30m"));
+        assert!(!matches("estimate<30m", "status: next"));
+    }
+
+    #[test]
+    fn text_and_lists() {
+        assert!(matches("body", "status: next"));
+        assert!(matches("\"body text\"", "status: next"));
+        assert!(matches("title:task", "status: next"));
+        assert!(matches("tag:#Urgent", "tags: [urgent, work]"));
+        assert!(matches("tag!=home", "tags: [urgent, work]"));
+        assert!(matches("project:website", "project: 00831_website"));
+    }
+
+    #[test]
+    fn rejects_bad_filters() {
+        let filters = [
+            "",
+            "(status:next",
+            "status:next)",
+            "tag<urgent",
+            "title>=a",
+            "context>@home",
+            "status<next",
+            "project>foo",
+            "priority>=2",
+            "OR",
+            "\"open",
+        ];
+        for filter in filters {
+            assert!(Query::parse(filter).is_err(), "{}", filter);
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use anyhow::Result;
+use chrono::{Datelike, Days, Duration, Months, NaiveDate, Weekday};
+use crate::dates::parse_weekday;
+
+/// Recurrence rule from a todo's `repeat:` field.
+///
+/// Accepted forms:
+/// - `daily`, `weekly`, `monthly`, `yearly`
+/// - `every day`, `every 2 weeks`, `every 3 months`, `every monday`
+/// - `weekly on friday`, `monthly on 1st`, `every 2 months on the 15th`
+/// - RRULE subset: `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO`, `FREQ=MONTHLY;BYMONTHDAY=1`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repeat {
+    freq: Freq,
+    interval: u32,
+    weekday: Option<Weekday>,
+    month_day: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Freq {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Repeat {
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let upper = input.to_uppercase();
+        let rule = upper.strip_prefix("RRULE:").unwrap_or(&upper);
+
+        let repeat = if rule.contains("FREQ=") {
+            Self::parse_rrule(rule)
+        } else {
+            Self::parse_words(&input.to_lowercase())
+        };
+
+        repeat.ok_or_else(|| anyhow::anyhow!("Invalid repeat rule: '{}'", input))
+    }
+
+    fn parse_words(input: &str) -> Option<Self> {
+        let words: Vec<&str> = input
+            .split_whitespace()
+            .filter(|w| *w != "the")
+            .collect();
+        let (head, on) = match words.iter().position(|w| *w == "on") {
+            Some(i) => (&words[..i], &words[i + 1..]),
+            None => (&words[..], &[][..]),
+        };
+
+        let mut repeat = match head {
+            [single] => Self::from_unit(single, 1).or_else(|| Self::from_adverb(single))?,
+            ["every", unit] => Self::from_unit(unit, 1)?,
+            ["every", n, unit] => Self::from_unit(unit, n.parse().ok().filter(|n| *n > 0)?)?,
+            _ => return None,
+        };
+
+        match on {
+            [] => {}
+            [day] if repeat.freq == Freq::Weekly => repeat.weekday = Some(parse_weekday(day)?),
+            [day] if repeat.freq == Freq::Monthly => repeat.month_day = Some(parse_month_day(day)?),
+            _ => return None,
+        }
+
+        Some(repeat)
+    }
+
+    fn from_adverb(word: &str) -> Option<Self> {
+        let freq = match word {
+            "daily" => Freq::Daily,
+            "weekly" => Freq::Weekly,
+            "monthly" => Freq::Monthly,
+            "yearly" | "annually" => Freq::Yearly,
+            _ => return None,
+        };
+        Some(Self::new(freq, 1))
+    }
+
+    fn from_unit(unit: &str, interval: u32) -> Option<Self> {
+        if let Some(weekday) = parse_weekday(unit) {
+            let mut repeat = Self::new(Freq::Weekly, interval);
+            repeat.weekday = Some(weekday);
+            return Some(repeat);
+        }
+
+        let freq = match unit.trim_end_matches('s') {
+            "day" => Freq::Daily,
+            "week" => Freq::Weekly,
+            "month" => Freq::Monthly,
+            "year" => Freq::Yearly,
+            _ => return None,
+        };
+        Some(Self::new(freq, interval))
+    }
+
+    fn parse_rrule(rule: &str) -> Option<Self> {
+        let mut repeat = Self::new(Freq::Daily, 1);
+        let mut has_freq = false;
+
+        for part in rule.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part.split_once('=')?;
+            match key {
+                "FREQ" => {
+                    repeat.freq = match value {
+                        "DAILY" => Freq::Daily,
+                        "WEEKLY" => Freq::Weekly,
+                        "MONTHLY" => Freq::Monthly,
+                        "YEARLY" => Freq::Yearly,
+                        _ => return None,
+                    };
+                    has_freq = true;
+                }
+                "INTERVAL" => repeat.interval = value.parse().ok().filter(|n| *n > 0)?,
+                "BYDAY" => repeat.weekday = Some(parse_weekday(&value.to_lowercase())?),
+                "BYMONTHDAY" => repeat.month_day = Some(parse_month_day(value)?),
+                _ => return None,
+            }
+        }
+
+        has_freq.then_some(repeat)
+    }
+
+    fn new(freq: Freq, interval: u32) -> Self {
+        Self {
+            freq,
+            interval,
+            weekday: None,
+            month_day: None,
+        }
+    }
+
+    /// The first occurrence after `due` that is also after `today`.
+    /// Monthly rules without a day keep `due`'s day of month (Jan 31 -> Feb 28 -> Mar 31).
+    pub fn next_due(&self, due: NaiveDate, today: NaiveDate) -> Option<NaiveDate> {
+        let mut rule = self.clone();
+        if rule.freq == Freq::Monthly && rule.month_day.is_none() {
+            rule.month_day = Some(due.day());
+        }
+
+        let mut next = rule.next_after(due)?;
+        while next <= today {
+            next = rule.next_after(next)?;
+        }
+        Some(next)
+    }
+
+    /// The first occurrence strictly after `date`
+    fn next_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match self.freq {
+            Freq::Daily => date.checked_add_days(Days::new(self.interval as u64)),
+            Freq::Weekly => match self.weekday {
+                Some(weekday) => {
+                    // 1..=7 days to the next such weekday, then whole weeks
+                    let (target, from) = (weekday.num_days_from_monday(), date.weekday().num_days_from_monday());
+                    let ahead = ((target + 6 - from) % 7 + 1) as u64;
+                    date.checked_add_days(Days::new(ahead + (self.interval as u64 - 1) * 7))
+                }
+                None => date.checked_add_days(Days::new(self.interval as u64 * 7)),
+            },
+            Freq::Monthly => match self.month_day {
+                Some(day) => {
+                    let this_month = with_month_day(date, day)?;
+                    if this_month > date {
+                        Some(this_month)
+                    } else {
+                        with_month_day(date.checked_add_months(Months::new(self.interval))?, day)
+                    }
+                }
+                None => date.checked_add_months(Months::new(self.interval)),
+            },
+            Freq::Yearly => date.checked_add_months(Months::new(self.interval.checked_mul(12)?)),
+        }
+    }
+}
+
+/// `1`, `1st`, `2nd`, `15th`, ... (1-31)
+fn parse_month_day(s: &str) -> Option<u32> {
+    let digits = s.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    digits.parse().ok().filter(|d| (1..=31).contains(d))
+}
+
+/// `date`'s month on `day`, clamped to the month's last day
+fn with_month_day(date: NaiveDate, day: u32) -> Option<NaiveDate> {
+    let first = date.with_day(1)?;
+    let last = (first.checked_add_months(Months::new(1))? - Duration::days(1)).day();
+    first.with_day(day.min(last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    /// The next due date of `rule` after `due`, with today before both
+    fn next(rule: &str, due: &str) -> String {
+        let repeat = Repeat::parse(rule).unwrap();
+        repeat.next_due(date(due), date("2000-01-01")).unwrap().to_string()
+    }
+
+    #[test]
+    fn parses_word_rules() {
+        let cases = [
+            ("daily", "2026-10-18", "2026-10-19"),
+            ("every day", "2026-10-18", "2026-10-19"),
+            ("weekly", "2026-10-18", "2026-10-25"),
+            ("every 2 weeks", "2026-10-18", "2026-11-01"),
+            ("every 3 months", "2026-10-18", "2027-01-18"),
+            ("yearly", "2026-10-18", "2027-10-18"),
+            ("every monday", "2026-10-18", "2026-10-19"),
+            ("weekly on friday", "2026-10-16", "2026-10-23"),
+            ("monthly on 1st", "2026-10-18", "2026-11-01"),
+            ("monthly on 1st", "2026-10-01", "2026-11-01"),
+            ("every 2 months on the 15th", "2026-10-18", "2026-12-15"),
+            ("monthly on 20th", "2026-10-18", "2026-10-20"),
+        ];
+        for (rule, due, expected) in cases {
+            assert_eq!(next(rule, due), expected, "{} from {}", rule, due);
+        }
+    }
+
+    #[test]
+    fn parses_rrule_subset() {
+        let cases = [
+            ("FREQ=DAILY;INTERVAL=3", "2026-10-18", "2026-10-21"),
+            ("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO", "2026-10-18", "2026-10-26"),
+            ("RRULE:FREQ=MONTHLY;BYMONTHDAY=1", "2026-10-18", "2026-11-01"),
+            ("freq=yearly", "2026-10-18", "2027-10-18"),
+        ];
+        for (rule, due, expected) in cases {
+            assert_eq!(next(rule, due), expected, "{} from {}", rule, due);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        for rule in ["", "sometimes", "every 0 days", "every 2", "weekly on 1st", "FREQ=HOURLY", "INTERVAL=2"] {
+            assert!(Repeat::parse(rule).is_err(), "{}", rule);
+        }
+    }
+
+    #[test]
+    fn monthly_rolls_over_month_end() {
+        assert_eq!(next("monthly", "2026-01-31"), "2026-02-28");
+        assert_eq!(next("monthly", "2028-01-31"), "2028-02-29");
+        assert_eq!(next("monthly on 31st", "2026-02-28"), "2026-03-31");
+        assert_eq!(next("monthly on 31st", "2026-03-31"), "2026-04-30");
+
+        // The day of month comes from the original due date, not the clamped one
+        let repeat = Repeat::parse("monthly").unwrap();
+        let today = date("2026-03-01");
+        assert_eq!(repeat.next_due(date("2026-01-31"), today), Some(date("2026-03-31")));
+    }
+
+    #[test]
+    fn huge_intervals_do_not_panic() {
+        for rule in ["every 4294967295 days", "every 4294967295 weeks", "FREQ=WEEKLY;INTERVAL=4294967295;BYDAY=MO", "every 400000000 years"] {
+            let repeat = Repeat::parse(rule).unwrap();
+            assert_eq!(repeat.next_due(date("2026-10-18"), date("2026-10-18")), None, "{}", rule);
+        }
+    }
+
+    #[test]
+    fn skips_occurrences_already_past() {
+        let repeat = Repeat::parse("weekly").unwrap();
+        let next = repeat.next_due(date("2026-10-01"), date("2026-10-18"));
+        assert_eq!(next, Some(date("2026-10-22")));
+    }
+}
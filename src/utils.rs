@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use chrono::Local;
 use std::fs;
+use std::io::{self, Write};
 use std::path::Path;
 use std::process::Command;
 
@@ -62,7 +63,7 @@ pub fn create_file_from_base_and_snip(
     Ok(())
 }
 
-fn apply_replacements(template: &str, replacements: &[(&str, &str)]) -> String {
+pub fn apply_replacements(template: &str, replacements: &[(&str, &str)]) -> String {
     let mut content = template.to_string();
     for (key, value) in replacements {
         let lowercase_key = format!("{{{{{}}}}}", key);
@@ -83,6 +84,52 @@ pub fn open_editor(file_path: &Path, editor: &str) -> Result<()> {
     Ok(())
 }
 
+/// Open the editor with the cursor placed on the given (1-based) line.
+/// VS Code style editors take `-g file:line`, everything else gets `+line file`.
+pub fn open_editor_at_line(file_path: &Path, editor: &str, line: usize) -> Result<()> {
+    let mut command = Command::new(editor);
+    let editor_name = Path::new(editor)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(editor);
+
+    if editor_name == "code" || editor_name == "codium" {
+        command.arg("-g").arg(format!("{}:{}", file_path.display(), line));
+    } else {
+        command.arg(format!("+{}", line)).arg(file_path);
+    }
+
+    command
+        .status()
+        .with_context(|| format!("Failed to open editor: {}", editor))?;
+
+    Ok(())
+}
+
+/// Ask for a 1-based selection from a numbered list. Returns the 0-based
+/// index, or `None` when the input is empty or invalid.
+pub fn prompt_selection(prompt: &str, len: usize) -> Result<Option<usize>> {
+    print!("{} (1-{}, or Enter to skip): ", prompt, len);
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let input = input.trim();
+
+    if input.is_empty() {
+        return Ok(None);
+    }
+
+    match input.parse::<usize>() {
+        Ok(selection) if selection >= 1 && selection <= len => Ok(Some(selection - 1)),
+        Ok(selection) => {
+            println!("Invalid selection: {}", selection);
+            Ok(None)
+        }
+        Err(_) => Ok(None),
+    }
+}
+
 pub fn sanitize_filename(s: &str) -> String {
     s.chars()
         .map(|c| match c {